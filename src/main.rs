@@ -1,1282 +1,1165 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::env;
 use std::fs::{self, File};
-use std::io::{self, Cursor, Read};
-use std::process::Command;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
-use reqwest::blocking::Client;
-use scraper::{Html, Selector};
-use serde::Deserialize;
-use serde_json::Value;
 use zip::ZipArchive;
-// Removed: use walkdir::WalkDir; // This import is not used
-use dirs;
+use walkdir::WalkDir;
 
 use eframe::egui;
 
-use tar::Archive;
-use flate2::read::GzDecoder;
-use xz2::read::XzDecoder;
-
-// Temurin API response structure
-#[derive(Deserialize)]
-struct TemurinAsset {
-    binary: Binary,
+use jdkm::*;
+
+/// Window title, also the base of the tray tooltip (translated at runtime).
+const APP_TITLE: &str = "Multi-Language Installer";
+
+/// Translated UI strings per language, keyed by their English text; `{}` marks a substituted value.
+/// English has no table: a missing entry falls back to the English key.
+fn translations() -> &'static HashMap<Language, HashMap<&'static str, &'static str>> {
+    static TRANSLATIONS: std::sync::OnceLock<HashMap<Language, HashMap<&'static str, &'static str>>> = std::sync::OnceLock::new();
+    TRANSLATIONS.get_or_init(|| {
+        let german: HashMap<&'static str, &'static str> = [
+            ("Multi-Language Installer", "Mehrsprachiger Installer"),
+            ("Exit", "Beenden"),
+            ("Installation Options", "Installationsoptionen"),
+            ("Settings", "Einstellungen"),
+            ("Install root, network, appearance and download options", "Installationsverzeichnis, Netzwerk, Darstellung und Downloads"),
+            ("Repair PATH", "PATH reparieren"),
+            ("Update all installed", "Alle installierten aktualisieren"),
+            ("Check every installed toolchain for a newer release and update the outdated ones", "Alle installierten Toolchains auf neuere Releases prüfen und veraltete aktualisieren"),
+            ("Checking installed toolchains for updates...", "Installierte Toolchains werden auf Updates geprüft..."),
+            ("All installed toolchains are up to date.", "Alle installierten Toolchains sind aktuell."),
+            ("The following toolchains will be updated, one after another:", "Die folgenden Toolchains werden nacheinander aktualisiert:"),
+            ("Update {} toolchains", "{} Toolchains aktualisieren"),
+            ("Updating {} ({} of {})...", "{} wird aktualisiert ({} von {})..."),
+            ("All updates finished.", "Alle Updates abgeschlossen."),
+            ("Updates stopped.", "Updates abgebrochen."),
+            ("Stop all", "Alle stoppen"),
+            ("Cancels every running installation, including a running \"Update all installed\"", "Bricht alle laufenden Installationen ab, auch ein laufendes \"Alle installierten aktualisieren\""),
+            ("Stop all installations", "Alle Installationen stoppen"),
+            ("Are you sure you want to stop all {} running installations?", "Möchten Sie wirklich alle {} laufenden Installationen stoppen?"),
+            ("The remaining updates of \"Update all installed\" are skipped.", "Die verbleibenden Updates von \"Alle installierten aktualisieren\" werden übersprungen."),
+            ("Yes, stop all", "Ja, alle stoppen"),
+            ("Find persisted PATH entries for managed toolchains that are broken or duplicated", "Defekte oder doppelte dauerhafte PATH-Einträge verwalteter Toolchains finden"),
+            ("{} Configuration", "{} – Konfiguration"),
+            ("Installs the fixed MinGW-w64 v11.0.0 release from SourceForge.", "Installiert das feste MinGW-w64-Release v11.0.0 von SourceForge."),
+            ("MinGW-w64 is only installed on Windows. On Linux/macOS, use the system compiler (e.g. build-essential, Xcode command line tools or Homebrew gcc).", "MinGW-w64 wird nur unter Windows installiert. Unter Linux/macOS den Systemcompiler verwenden (z. B. build-essential, Xcode-Befehlszeilentools oder Homebrew-gcc)."),
+            ("Rust is installed with rustup on the stable channel; use rustup itself to add toolchains and components.", "Rust wird mit rustup aus dem stable-Kanal installiert; weitere Toolchains und Komponenten mit rustup selbst hinzufügen."),
+            ("The Windows embeddable Python package has no pip; get-pip.py is downloaded to bootstrap it before libraries are installed.", "Das einbettbare Python-Paket für Windows enthält kein pip; vor der Installation von Bibliotheken wird es mit get-pip.py nachgerüstet."),
+            ("Precompiled Erlang/OTP builds are only published for 64-bit Windows. On Linux/macOS, use your package manager, kerl or asdf.", "Vorkompilierte Erlang/OTP-Builds gibt es nur für 64-Bit-Windows. Unter Linux/macOS den Paketmanager, kerl oder asdf verwenden."),
+            ("Elixir needs Erlang/OTP, and the Elixir build is picked to match the installed OTP major version.", "Elixir benötigt Erlang/OTP; der Elixir-Build wird passend zur installierten OTP-Hauptversion gewählt."),
+            ("Ruby is installed with RubyInstaller, without the MSYS2 DevKit; run `ridk install` before installing gems with native extensions.", "Ruby wird mit RubyInstaller ohne das MSYS2-DevKit installiert; vor der Installation von Gems mit nativen Erweiterungen `ridk install` ausführen."),
+            ("Ruby is only installed on Windows. On Linux/macOS, use rbenv with ruby-build, asdf or your package manager.", "Ruby wird nur unter Windows installiert. Unter Linux/macOS rbenv mit ruby-build, asdf oder den Paketmanager verwenden."),
+            ("Oracle JDK is distributed under the Oracle No-Fee Terms and Conditions, which must be accepted below before installing.", "Oracle JDK unterliegt den Oracle No-Fee Terms and Conditions, die vor der Installation unten akzeptiert werden müssen."),
+            ("GraalVM for JDK 21 and later includes native-image but no gu, so the JavaScript and Python components can only be added to older releases, which install them with the bundled gu.", "GraalVM für JDK 21 und neuer enthält native-image, aber kein gu; die JavaScript- und Python-Komponenten lassen sich daher nur älteren Versionen hinzufügen, die sie mit dem mitgelieferten gu installieren."),
+            ("OpenJDK builds are found by reading jdk.java.net, so a change to that site can break version resolution.", "OpenJDK-Builds werden über jdk.java.net ermittelt; Änderungen an dieser Seite können die Versionsauflösung stören."),
+            ("Requires a JDK; install one from a Java tab first.", "Benötigt ein JDK; zuerst eines über einen Java-Tab installieren."),
+            ("Install Latest Version", "Neueste Version installieren"),
+            ("Enter a Java version, e.g. 21 or 21.0.2.", "Geben Sie eine Java-Version ein, z. B. 21 oder 21.0.2."),
+            ("Enter a Python version as major.minor or major.minor.patch, e.g. 3.12 or 3.12.4.", "Geben Sie eine Python-Version als Major.Minor oder Major.Minor.Patch ein, z. B. 3.12 oder 3.12.4."),
+            ("{} not installed: {}", "{} nicht installiert: {}"),
+            ("Version:", "Version:"),
+            ("Browse versions", "Versionen durchsuchen"),
+            ("List the releases available for this vendor", "Verfügbare Releases dieses Anbieters auflisten"),
+            ("Latest supported version", "Neueste unterstützte Version"),
+            ("(This installer attempts to install the latest supported {} version.)", "(Dieser Installer installiert die neueste unterstützte {}-Version.)"),
+            ("Include JavaFX", "JavaFX einschließen"),
+            ("I accept the license terms", "Ich akzeptiere die Lizenzbedingungen"),
+            ("View license", "Lizenz anzeigen"),
+            ("Downloads are refused until the license is accepted.", "Downloads werden verweigert, bis die Lizenz akzeptiert ist."),
+            ("This vendor does not publish JDK packages bundling JavaFX.", "Dieser Anbieter veröffentlicht keine JDK-Pakete mit JavaFX."),
+            ("JVM Implementation", "JVM-Implementierung"),
+            ("Release Type", "Release-Typ"),
+            ("General availability (ga)", "Allgemein verfügbar (ga)"),
+            ("Early access (ea)", "Vorabversion (ea)"),
+            ("Python Libraries (e.g., 'numpy==1.20.0, pandas>=1.3.0'):", "Python-Bibliotheken (z. B. 'numpy==1.20.0, pandas>=1.3.0'):"),
+            ("Copy install command", "Installationsbefehl kopieren"),
+            ("Copies the equivalent command-line invocation for this tab's settings", "Kopiert den entsprechenden Kommandozeilenaufruf für die Einstellungen dieses Tabs"),
+            ("Install", "Installieren"),
+            ("Ready for installation", "Bereit zur Installation"),
+            ("Starting installation process...", "Installation wird gestartet..."),
+            ("{} {} installed successfully", "{} {} erfolgreich installiert"),
+            ("{} installation failed: {}", "Installation von {} fehlgeschlagen: {}"),
+            ("latest", "neueste Version"),
+            ("Installation complete!", "Installation abgeschlossen!"),
+            ("Installation failed.", "Installation fehlgeschlagen."),
+            ("Cancel", "Abbrechen"),
+            ("Installation Complete!", "Installation abgeschlossen!"),
+            ("Installation Failed: {}", "Installation fehlgeschlagen: {}"),
+            ("Show log", "Protokoll anzeigen"),
+            ("Current Status", "Aktueller Status"),
+            ("Downloading...", "Wird heruntergeladen..."),
+            ("Extracting...", "Wird entpackt..."),
+            ("Installing libraries...", "Bibliotheken werden installiert..."),
+            ("Cancel Installation", "Installation abbrechen"),
+            ("Python Details", "Python-Details"),
+            ("Python Version", "Python-Version"),
+            ("Library Compatibility", "Bibliothekskompatibilität"),
+            ("Full Output Log (Python related only)", "Vollständiges Ausgabeprotokoll (nur Python)"),
+            ("Detailed Output Log ({})", "Detailliertes Ausgabeprotokoll ({})"),
+            ("Installation", "Installation"),
+            ("Architecture", "Architektur"),
+            ("Java install base:", "Java-Basisverzeichnis:"),
+            ("Java vendors install into <base>/<vendor>-<version> when set; leave empty to use the install root.", "Java-Anbieter werden, falls gesetzt, nach <Basis>/<Anbieter>-<Version> installiert; leer lassen für das Installationsverzeichnis."),
+            ("Update /etc/profile.d/jdkm-java.sh after Java installs", "/etc/profile.d/jdkm-java.sh nach Java-Installationen aktualisieren"),
+            ("Sets JAVA_HOME and PATH for all users' login shells; requires running as root", "Setzt JAVA_HOME und PATH für die Login-Shells aller Benutzer; erfordert Root-Rechte"),
+            ("Detect", "Erkennen"),
+            ("Detect uses the native architecture, including on Apple Silicon when running under Rosetta", "Erkennen verwendet die native Architektur, auch auf Apple Silicon unter Rosetta"),
+            ("Install root:", "Installationsverzeichnis:"),
+            ("Default for this platform", "Standard für diese Plattform"),
+            ("Toolchains are installed under {}", "Toolchains werden unter {} installiert"),
+            ("Maintain a \"current\" link per vendor", "Je Anbieter einen \"current\"-Link pflegen"),
+            ("Points <vendor>_versions/current at the last installed version, so a PATH entry using it survives version switches", "Lässt <Anbieter>_versions/current auf die zuletzt installierte Version zeigen, damit ein PATH-Eintrag darüber Versionswechsel übersteht"),
+            ("Network", "Netzwerk"),
+            ("Proxy:", "Proxy:"),
+            ("Download Timeout (s)", "Download-Timeout (s)"),
+            ("Log Size Limit per Tab (KiB)", "Log-Größenlimit pro Tab (KiB)"),
+            ("Older log lines are dropped from the window past this size; the full log is kept in the logs folder of the config directory", "Ältere Logzeilen werden ab dieser Größe aus dem Fenster entfernt; das vollständige Log liegt im Ordner logs des Konfigurationsverzeichnisses"),
+            ("API Retries", "API-Wiederholungen"),
+            ("API Retry Backoff (ms)", "Wartezeit vor API-Wiederholung (ms)"),
+            ("Download Retries", "Download-Wiederholungen"),
+            ("Download Retry Backoff (ms)", "Wartezeit vor Download-Wiederholung (ms)"),
+            ("The backoff doubles after each failed retry.", "Die Wartezeit verdoppelt sich nach jeder fehlgeschlagenen Wiederholung."),
+            ("Max Parallel Downloads", "Max. parallele Downloads"),
+            ("Bandwidth Cap per Download (KiB/s, 0 = unlimited)", "Bandbreitenlimit pro Download (KiB/s, 0 = unbegrenzt)"),
+            ("Mirrors", "Spiegelserver"),
+            ("Mirrors disabled: {}", "Spiegelserver deaktiviert: {}"),
+            ("Key: a vendor ID or host name. Value: the base URL replacing the download URL's scheme and host.", "Schlüssel: eine Anbieter-ID oder ein Hostname. Wert: die Basis-URL, die Schema und Host der Download-URL ersetzt."),
+            ("Remove", "Entfernen"),
+            ("Configuration Bundle", "Konfigurationspaket"),
+            ("Settings and mirrors in one zip file, to set up another machine the same way. Mirror secrets stay in the keychain.", "Einstellungen und Spiegelserver in einer ZIP-Datei, um einen anderen Rechner genauso einzurichten. Geheimnisse der Spiegelserver bleiben im Schlüsselbund."),
+            ("File:", "Datei:"),
+            ("Export bundle", "Paket exportieren"),
+            ("Import bundle", "Paket importieren"),
+            ("Exported {} files to {}", "{} Dateien nach {} exportiert"),
+            ("Imported {} files from {}", "{} Dateien aus {} importiert"),
+            ("Bundle failed: {}", "Paket fehlgeschlagen: {}"),
+            ("Interrupted Installations", "Abgebrochene Installationen"),
+            ("These installations did not finish last time. Resume one to install it again on its tab, or clean them all up:", "Diese Installationen wurden beim letzten Mal nicht abgeschlossen. Eine fortsetzen, um sie in ihrem Tab erneut zu installieren, oder alle bereinigen:"),
+            ("Resume", "Fortsetzen"),
+            ("Installs this version again; a partial download continues where it stopped", "Installiert diese Version erneut; ein unvollständiger Download wird an der Abbruchstelle fortgesetzt"),
+            ("partially installed", "teilweise installiert"),
+            ("not yet extracted", "noch nicht entpackt"),
+            ("Keep for now", "Vorerst behalten"),
+            ("Installing the same version again also replaces the leftovers", "Eine erneute Installation derselben Version ersetzt die Reste ebenfalls"),
+            ("Mirror credentials", "Zugangsdaten für Spiegelserver"),
+            ("Sent only to the exact host. Leave the user empty to send the secret as a bearer token. Secrets are kept in the OS keychain.", "Werden nur an genau diesen Host gesendet. Ohne Benutzer wird das Geheimnis als Bearer-Token gesendet. Geheimnisse liegen im Schlüsselbund des Betriebssystems."),
+            ("User", "Benutzer"),
+            ("Password or token (unchanged)", "Passwort oder Token (unverändert)"),
+            ("Add credentials", "Zugangsdaten hinzufügen"),
+            ("Add mirror", "Spiegelserver hinzufügen"),
+            ("Save mirrors", "Spiegelserver speichern"),
+            ("Appearance", "Darstellung"),
+            ("Language", "Sprache"),
+            ("High Contrast Theme", "Kontrastreiches Design"),
+            ("Font Size", "Schriftgröße"),
+            ("UI Scale", "UI-Skalierung"),
+            ("Compact View", "Kompaktansicht"),
+            ("Collapse the status and log panels into a single line", "Status- und Protokollbereiche auf eine Zeile reduzieren"),
+            ("Mirrors saved", "Spiegelserver gespeichert"),
+            ("Could not save mirrors: {}", "Spiegelserver konnten nicht gespeichert werden: {}"),
+            ("Could not save settings: {}", "Einstellungen konnten nicht gespeichert werden: {}"),
+            ("Available {} versions", "Verfügbare {}-Versionen"),
+            ("Fetching available versions...", "Verfügbare Versionen werden abgerufen..."),
+            ("Failed to fetch versions: {}", "Versionen konnten nicht abgerufen werden: {}"),
+            ("Double-click a version to use it.", "Doppelklicken Sie auf eine Version, um sie zu übernehmen."),
+            ("Version", "Version"),
+            ("Release date", "Veröffentlichung"),
+            ("Yes", "Ja"),
+            ("No broken or duplicated PATH entries for managed toolchains were found.", "Es wurden keine defekten oder doppelten PATH-Einträge für verwaltete Toolchains gefunden."),
+            ("The following persisted PATH entries point into managed toolchain directories and can be removed:", "Die folgenden dauerhaften PATH-Einträge verweisen auf verwaltete Toolchain-Verzeichnisse und können entfernt werden:"),
+            ("Clean up", "Bereinigen"),
+            ("Close", "Schließen"),
+            ("Cancel Confirmation", "Abbruch bestätigen"),
+            ("Are you sure you want to stop the installation?", "Möchten Sie die Installation wirklich abbrechen?"),
+            ("Yes, stop", "Ja, abbrechen"),
+            ("No, continue", "Nein, fortfahren"),
+            ("Installation cancelled.", "Installation abgebrochen."),
+            ("Exit Confirmation", "Beenden bestätigen"),
+            ("Are you sure you want to exit the application?", "Möchten Sie die Anwendung wirklich beenden?"),
+            ("Yes, exit", "Ja, beenden"),
+            ("No, stay", "Nein, bleiben"),
+            ("Fetching the published checksum...", "Veröffentlichte Prüfsumme wird abgerufen..."),
+            ("Resuming download from {}%", "Download wird bei {}% fortgesetzt"),
+            ("Counting archive entries...", "Archiveinträge werden gezählt..."),
+            ("Checksum verification failed", "Prüfsummenprüfung fehlgeschlagen"),
+            ("Checking for existing installations...", "Vorhandene Installationen werden geprüft..."),
+            ("Checking pip availability...", "Verfügbarkeit von pip wird geprüft..."),
+            ("Downloading pip installer...", "pip-Installer wird heruntergeladen..."),
+            ("Extracting files, almost there...", "Dateien werden entpackt, fast fertig..."),
+            ("Finding latest Python version...", "Neueste Python-Version wird gesucht..."),
+            ("Finding latest Microsoft Build of OpenJDK...", "Neuester Microsoft Build of OpenJDK wird gesucht..."),
+            ("Finding latest Oracle JDK {} update...", "Neuestes Update von Oracle JDK {} wird gesucht..."),
+            ("Installing Python libraries...", "Python-Bibliotheken werden installiert..."),
+            ("Finding GraalVM Community Edition release...", "GraalVM-Community-Edition-Release wird gesucht..."),
+            ("Finding Liberica JDK release...", "Liberica-JDK-Release wird gesucht..."),
+            ("Finding SapMachine release...", "SapMachine-Release wird gesucht..."),
+            ("Finding latest Kotlin release...", "Neuestes Kotlin-Release wird gesucht..."),
+            ("Running RubyInstaller...", "RubyInstaller wird ausgeführt..."),
+            ("Finding latest Deno release...", "Neuestes Deno-Release wird gesucht..."),
+            ("Finding Bun release...", "Bun-Release wird gesucht..."),
+            ("Reading the Zig download index...", "Zig-Downloadindex wird gelesen..."),
+            ("Add to PATH permanently", "Dauerhaft zum PATH hinzufügen"),
+            ("Append the install's bin directory to your user PATH (the registry on Windows, your shell profile elsewhere), so new terminals find it", "Das bin-Verzeichnis der Installation an den Benutzer-PATH anhängen (unter Windows in der Registry, sonst im Shell-Profil), damit neue Terminals es finden"),
+            ("Installing GraalVM components...", "GraalVM-Komponenten werden installiert..."),
+            ("GraalVM components step finished.", "GraalVM-Komponenten-Schritt abgeschlossen."),
+            ("GraalVM components:", "GraalVM-Komponenten:"),
+            ("Installing pip...", "pip wird installiert..."),
+            ("Missing prerequisite: {}", "Fehlende Voraussetzung: {}"),
+            ("Finding the latest OpenJDK release on jdk.java.net...", "Neueste OpenJDK-Version auf jdk.java.net wird gesucht..."),
+            ("Preparing {} installation...", "Installation von {} wird vorbereitet..."),
+            ("Checking free disk space...", "Freier Speicherplatz wird geprüft..."),
+            ("Not enough disk space", "Nicht genug Speicherplatz"),
+            ("Preparing C/C++ (MinGW-w64) installation...", "Installation von C/C++ (MinGW-w64) wird vorbereitet..."),
+            ("Running rustup installer...", "rustup-Installer wird ausgeführt..."),
+            ("Waiting for a free download slot...", "Warten auf einen freien Download-Platz..."),
+            ("Downloading {}...", "{} wird heruntergeladen..."),
+            ("Downloading... {} / {} at {}/s, ETA {}", "Wird heruntergeladen... {} / {} mit {}/s, noch {}"),
+            ("Downloading... {} at {}/s", "Wird heruntergeladen... {} mit {}/s"),
+            ("Extracting... {}%", "Wird entpackt... {}%"),
+            ("Library compatibility issue for {}: Expected {}, got {}.", "Kompatibilitätsproblem bei {}: erwartet {}, gefunden {}."),
+            ("Python library installation failed: {}.", "Installation der Python-Bibliothek fehlgeschlagen: {}."),
+            ("Python version mismatch: Expected {}, got {}.", "Python-Version stimmt nicht überein: erwartet {}, gefunden {}."),
+            ("Verifying {} installation...", "Installation von {} wird überprüft..."),
+            ("{} installation complete!", "Installation von {} abgeschlossen!"),
+            ("{} is already installed.", "{} ist bereits installiert."),
+            ("{} verification failed.", "Überprüfung von {} fehlgeschlagen."),
+            ("Installing library {} of {}: {}", "Bibliothek {} von {} wird installiert: {}"),
+            ("Verifying library {} of {}: {}", "Bibliothek {} von {} wird überprüft: {}"),
+            ("Installing {} Python libraries in one pip call...", "{} Python-Bibliotheken werden in einem pip-Aufruf installiert..."),
+            ("Install libraries in one pip call", "Bibliotheken in einem pip-Aufruf installieren"),
+            ("Faster for long lists; falls back to one library at a time if the combined install fails", "Schneller bei langen Listen; bei einem Fehler wird jede Bibliothek einzeln installiert"),
+            ("Installed {} Python libraries.", "{} Python-Bibliotheken installiert."),
+            ("{} - installing {}%", "{} - Installation {}%"),
+            ("Show tray icon", "Symbol im Infobereich anzeigen"),
+            ("Shows install progress in the system tray, with Restore and Quit", "Zeigt den Installationsfortschritt im Infobereich, mit Wiederherstellen und Beenden"),
+            ("No tray icon: {}", "Kein Symbol im Infobereich: {}"),
+            ("Restore", "Wiederherstellen"),
+            ("Copy URL", "URL kopieren"),
+            ("Save log…", "Log speichern…"),
+            ("Saves the full log of the last run to a file, e.g. to attach to a bug report", "Speichert das vollständige Log des letzten Laufs in eine Datei, z. B. für einen Fehlerbericht"),
+            ("Log saved to {}", "Log gespeichert unter {}"),
+            ("Could not save the log: {}", "Log konnte nicht gespeichert werden: {}"),
+            ("Copy log", "Log kopieren"),
+            ("Show:", "Anzeigen:"),
+            ("All messages", "Alle Meldungen"),
+            ("Warnings and errors", "Warnungen und Fehler"),
+            ("Errors only", "Nur Fehler"),
+            ("All steps", "Alle Schritte"),
+            ("General", "Allgemein"),
+            ("Download", "Download"),
+            ("Extract", "Entpacken"),
+            ("Verify", "Überprüfung"),
+            ("Pip", "Pip"),
+            ("Copies the full log of the last run to the clipboard", "Kopiert das vollständige Log des letzten Laufs in die Zwischenablage"),
+            ("Log copied to the clipboard", "Log in die Zwischenablage kopiert"),
+            ("Copies the download URL resolved by the last run", "Kopiert die beim letzten Lauf ermittelte Download-URL"),
+            ("Export SBOM", "SBOM exportieren"),
+            ("Verify integrity", "Integrität prüfen"),
+            ("Uninstall…", "Deinstallieren…"),
+            ("Include in \"Install all selected\"", "In \"Alle ausgewählten installieren\" einbeziehen"),
+            ("Install all selected ({})", "Alle ausgewählten installieren ({})"),
+            ("Installs every ticked tab with its own settings at the same time, except that Elixir and the JVM tools wait for a ticked Erlang or JDK; downloads share the parallel download limit", "Installiert alle angehakten Tabs gleichzeitig mit ihren eigenen Einstellungen, nur Elixir und die JVM-Werkzeuge warten auf ein angehaktes Erlang bzw. JDK; Downloads teilen sich das Limit paralleler Downloads"),
+            ("Installed versions:", "Installierte Versionen:"),
+            ("Refresh", "Aktualisieren"),
+            ("Scans the install directory again", "Durchsucht das Installationsverzeichnis erneut"),
+            ("Scanning...", "Wird durchsucht..."),
+            ("None", "Keine"),
+            ("unverified", "nicht geprüft"),
+            ("Active", "Aktiv"),
+            ("Set as active", "Als aktiv setzen"),
+            ("Points the current link at this version and puts the link on PATH", "Richtet den current-Link auf diese Version aus und trägt den Link in PATH ein"),
+            ("Uninstall", "Deinstallieren"),
+            ("Removes an installed version together with the PATH and home variable entries this tool set up for it", "Entfernt eine installierte Version samt der PATH- und Home-Variablen-Einträge, die dieses Tool dafür angelegt hat"),
+            ("Uninstall Confirmation", "Deinstallation bestätigen"),
+            ("Are you sure you want to uninstall {} {}? This deletes:", "Möchten Sie {} {} wirklich deinstallieren? Dabei wird gelöscht:"),
+            ("Yes, uninstall", "Ja, deinstallieren"),
+            ("No, keep", "Nein, behalten"),
+            ("No installed versions of {} were found.", "Es wurden keine installierten Versionen von {} gefunden."),
+            ("Compares the installed files with the hashes recorded at install time", "Vergleicht die installierten Dateien mit den bei der Installation erfassten Prüfsummen"),
+            ("Recording file hashes...", "Datei-Prüfsummen werden erfasst..."),
+            ("Writes a CycloneDX bill of materials for the last install next to its directory", "Schreibt eine CycloneDX-Stückliste der letzten Installation neben ihr Verzeichnis"),
+            ("Distribution", "Distribution"),
+            ("Libraries are installed with conda install.", "Bibliotheken werden mit conda install installiert."),
+            ("Finding Miniconda installer...", "Miniconda-Installer wird gesucht..."),
+            ("Running Miniconda installer...", "Miniconda-Installer wird ausgeführt..."),
+        ].into_iter().collect();
+        HashMap::from([(Language::German, german)])
+    })
 }
 
-#[derive(Deserialize)]
-struct Binary {
-    package: Package,
+/// Looks up the translation of a fixed UI string.
+fn tr(language: Language, english: &'static str) -> &'static str {
+    translations().get(&language).and_then(|table| table.get(english)).copied().unwrap_or(english)
 }
 
-#[derive(Deserialize)]
-struct Package {
-    name: String,
-    link: String,
+/// Translates `template` and substitutes `args` for its `{}` placeholders in order.
+fn tr_format(language: Language, template: &'static str, args: &[&dyn std::fmt::Display]) -> String {
+    fill_template(tr(language, template), args)
 }
 
-/// Detects the operating system and architecture.
-/// Returns a tuple of `(os_name, arch)` or `None` if unsupported.
-/// The `arch` value is adjusted for different vendor APIs (e.g., "x86_64" becomes "x64" for Azul, "arm64" for Node.js).
-fn detect_platform() -> Option<(&'static str, &'static str)> {
-    let os = env::consts::OS;
-    let arch = env::consts::ARCH; // Use raw arch and map later based on vendor needs
+/// Translates a status reported by an installation: its template is looked up, then filled.
+fn translate_status(language: Language, status: &Status) -> String {
+    status.fill(tr(language, status.template))
+}
 
-    match os {
-        "windows" => Some(("windows", arch)),
-        "macos"   => Some(("darwin",   arch)), // Node.js expects darwin
-        "linux"   => Some(("linux",   arch)),
-        _         => None,
-    }
+/// Where each tab's full install log is written, `<config dir>/toolchain/logs/<vendor>.log`.
+fn install_log_path(vendor: &str) -> Option<PathBuf> {
+    toolchain_config_dir().map(|dir| dir.join("logs").join(format!("{}.log", vendor)))
 }
 
-/// Helper function to compare versions. Supports "==" and ">=".
-/// Performs a simple string comparison. For more complex version specifiers (e.g., "~=", "^"),
-/// a dedicated version parsing library would be required.
-fn is_version_compatible(installed_version: &str, required_specifier: &str) -> bool {
-    if required_specifier.contains("==") {
-        let parts: Vec<&str> = required_specifier.split("==").collect();
-        if parts.len() == 2 {
-            return installed_version == parts[1].trim();
-        }
-    } else if required_specifier.contains(">=") {
-        let parts: Vec<&str> = required_specifier.split(">=").collect();
-        if parts.len() == 2 {
-            let required_version_str = parts[1].trim();
-            // Simple string comparison for now. This assumes lexicographical comparison works for
-            // simple cases (e.g., "3.9.1" >= "3.9.0") but might fail for complex ones
-            // (e.g., "1.10.0" vs "1.2.0").
-            return installed_version >= required_version_str;
-        }
-    } else {
-        // If no specifier, assume exact match or general compatibility
-        return installed_version == required_specifier;
-    }
-    false
-}
-
-/// Fetches the latest stable Python 3.x version from python.org.
-fn get_latest_python_version() -> Result<String, String> {
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Python version check HTTP client failed: {}", e))?;
-
-    let resp = client.get("https://www.python.org/downloads/")
-        .send().map_err(|e| format!("Failed to reach python.org: {}", e))?
-        .text().map_err(|e| format!("Failed to read python.org HTML: {}", e))?;
-
-    let document = Html::parse_document(&resp);
-    // Selector for the latest stable Python 3 release link.
-    // This selector targets the link with class 'release-download-v3' within the 'download-for-current-os' section.
-    let selector = Selector::parse(".download-for-current-os .release-download-v3").map_err(|e| format!("Failed to parse selector for Python version: {:?}", e))?;
-
-    if let Some(element) = document.select(&selector).next() {
-        if let Some(href) = element.value().attr("href") {
-            // Example: /ftp/python/3.12.4/Python-3.12.4.tgz or /ftp/python/3.12.4/python-3.12.4-embed-amd64.zip
-            let parts: Vec<&str> = href.split('/').collect();
-            if let Some(filename) = parts.last() {
-                // Extract version from filename like "Python-3.12.4.tgz" or "python-3.12.4-embed-amd64.zip"
-                if filename.contains("Python-") {
-                    let version_part = filename.replace("Python-", "").replace(".tgz", "").replace(".zip", "").replace("-embed-amd64", "");
-                    return Ok(version_part);
-                } else if filename.contains("python-") { // For embeddable zips
-                    let version_part = filename.replace("python-", "").replace("-embed-amd64.zip", "");
-                    return Ok(version_part);
-                }
-            }
-        }
+/// Prefix marking warnings and errors in the log file and in exported or printed logs.
+fn level_prefix(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Info => "",
+        LogLevel::Warn => "WARNING: ",
+        LogLevel::Error => "ERROR: ",
     }
-    Err("Could not find the latest Python 3.x version on python.org. Please try a specific version.".to_string())
 }
 
-/// Fetches the latest stable Go version from go.dev/dl/.
-fn get_latest_go_version(os_name: &str, arch: &str) -> Result<(String, String, bool), String> {
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Go version check HTTP client failed: {}", e))?;
+/// A tab's install log, as typed entries. The entries kept for the UI are capped at `max_len`
+/// bytes of text: past that, the oldest are dropped down to half the cap, so trimming stays rare
+/// on long installs. Everything pushed is also appended to the run's log file, which is never trimmed.
+struct InstallLog {
+    entries: Vec<LogEntry>,
+    len: usize, // Bytes of message text in `entries`
+    max_len: usize,
+    file: Option<(File, PathBuf)>,
+    trimmed: bool, // Entries were dropped since the last restart
+}
 
-    let resp = client.get("https://go.dev/dl/")
-        .send().map_err(|e| format!("Failed to reach go.dev/dl/: {}", e))?
-        .text().map_err(|e| format!("Failed to read go.dev/dl/ HTML: {}", e))?;
+impl InstallLog {
+    fn new() -> Self {
+        InstallLog { entries: Vec::new(), len: 0, max_len: DEFAULT_MAX_LOG_KIB * 1024, file: None, trimmed: false }
+    }
 
-    let document = Html::parse_document(&resp);
-    let toggle_button_selector = Selector::parse(".toggleButton").map_err(|e| format!("Failed to parse toggleButton selector for Go version: {:?}", e))?;
-    let download_table_selector = Selector::parse(".downloadTable a").map_err(|e| format!("Failed to parse downloadTable selector for Go version: {:?}", e))?;
+    /// Clears the log for a new run of `vendor` and truncates its log file. Without a writable
+    /// config directory the log is only kept in memory.
+    fn restart(&mut self, vendor: &str, max_len: usize) {
+        self.clear();
+        self.max_len = max_len.max(1024);
+        self.file = install_log_path(vendor).and_then(|path| {
+            fs::create_dir_all(path.parent()?).ok()?;
+            File::create(&path).ok().map(|file| (file, path))
+        });
+    }
 
-    let mut latest_go_version: Option<String> = None;
+    /// The entries shown in the UI.
+    fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
 
-    // Find the latest version from the toggle buttons
-    for element in document.select(&toggle_button_selector) {
-        let text = element.text().collect::<String>();
-        if text.contains("(latest)") {
-            latest_go_version = text.split_whitespace().next().map(|s| s.to_string());
-            break;
-        }
+    /// Heads the entries once older ones have been dropped, saying where the full log is.
+    fn trim_notice(&self) -> Option<String> {
+        self.trimmed.then(|| match &self.file {
+            Some((_, path)) => format!("[Earlier output trimmed; the full log is in {}]", path.display()),
+            None => "[Earlier output trimmed]".to_string(),
+        })
     }
 
-    let go_version = latest_go_version.ok_or("Could not find the latest Go version on go.dev/dl/.".to_string())?;
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.len = 0;
+        self.trimmed = false;
+    }
 
-    let go_arch = match arch {
-        "x86_64" => "amd64",
-        "aarch64" => "arm64",
-        _ => return Err(format!("Unsupported architecture for Go: {}", arch)),
-    };
+    fn push(&mut self, level: LogLevel, category: LogCategory, message: &str) {
+        if let Some((file, _)) = &mut self.file {
+            let _ = write!(file, "{}{}", level_prefix(level), message); // A full disk must not break the install
+        }
+        self.len += message.len();
+        self.entries.push(LogEntry { level, category, message: message.to_string() });
+        if self.len > self.max_len {
+            self.trim();
+        }
+    }
 
-    let file_extension = if os_name == "windows" { ".zip" } else { ".tar.gz" };
-    let expected_link_part = format!("{}-{}{}", os_name, go_arch, file_extension);
+    /// The whole log of the current run: the log file when there is one, since it is never
+    /// trimmed, otherwise the entries shown in the UI.
+    fn full_text(&self) -> String {
+        self.file.as_ref()
+            .and_then(|(_, path)| fs::read_to_string(path).ok())
+            .unwrap_or_else(|| self.entries.iter().map(|entry| format!("{}{}", level_prefix(entry.level), entry.message)).collect())
+    }
 
-    for element in document.select(&download_table_selector) {
-        if let Some(href) = element.value().attr("href") {
-            if href.contains(&go_version) && href.contains(&expected_link_part) {
-                let download_url = format!("https://go.dev{}", href);
-                let pkg_name = href.split('/').last().unwrap_or("go_package").to_string();
-                let is_zip = file_extension == ".zip";
-                return Ok((download_url, pkg_name, is_zip));
-            }
+    /// Drops the oldest entries until about half of `max_len` remains, always keeping the newest.
+    fn trim(&mut self) {
+        let mut dropped = 0;
+        while self.len > self.max_len / 2 && dropped + 1 < self.entries.len() {
+            self.len -= self.entries[dropped].message.len();
+            dropped += 1;
         }
+        self.entries.drain(..dropped);
+        self.trimmed = true;
     }
-
-    Err(format!("Could not find Go download link for version {} on {}/{}", go_version, os_name, go_arch))
 }
 
+/// Shows the entries of `log` that are at least `min_level` and, if given, in `category`;
+/// errors in red and warnings in yellow.
+fn show_log_entries(ui: &mut egui::Ui, log: &InstallLog, min_level: LogLevel, category: Option<LogCategory>) {
+    if let Some(notice) = log.trim_notice() {
+        ui.monospace(notice);
+    }
+    let shown = log.entries().iter()
+        .filter(|entry| entry.level >= min_level && category.is_none_or(|category| entry.category == category));
+    for entry in shown {
+        let message = entry.message.trim_end_matches(['\r', '\n']);
+        if message.is_empty() {
+            continue;
+        }
+        let text = egui::RichText::new(message).monospace();
+        ui.label(match entry.level {
+            LogLevel::Info => text,
+            LogLevel::Warn => text.color(egui::Color32::YELLOW),
+            LogLevel::Error => text.color(egui::Color32::from_rgb(255, 100, 100)),
+        });
+    }
+}
 
-/// Core installation logic, refactored to take a mutable String for logging.
-/// Returns Ok(()) on success, Err(String) on failure.
-fn run_installation_logic(
-    vendor: &str,
-    version: &str,
-    install_latest_flag: bool,
-    python_libraries: &str, // New parameter for Python libraries
-    log_output: Arc<Mutex<String>>, // Changed to Arc<Mutex<String>>
-    ctx: egui::Context, // Pass context to update UI from thread
-    app_state_id: egui::Id, // Pass ID to access app state in context
-    cancel_requested: Arc<AtomicBool>, // Cancellation flag
-) -> Result<(), String> {
-    // Helper to update app state and request repaint
-    let update_app_state = |
-        ctx: &egui::Context,
-        app_state_id: egui::Id,
-        vendor_name: &str, // New parameter to identify which language's state to update
-        status: Option<String>,
-        download_progress: Option<f32>,
-        extract_progress: Option<f32>,
-    | {
-        if let Some(app_state_arc) = ctx.data(|d| d.get_temp::<Arc<Mutex<JdkInstallerApp>>>(app_state_id)) {
-            let mut app_state = app_state_arc.lock().expect("Failed to acquire app state lock in update_app_state");
-            if let Some(lang_state) = app_state.language_states.get_mut(vendor_name) {
-                if let Some(s) = status {
-                    lang_state.current_status = s;
-                }
-                if let Some(dp) = download_progress {
-                    lang_state.download_progress = dp;
+/// Level and step filters for a tab's log view.
+fn log_filter_controls(ui: &mut egui::Ui, lang: Language, min_level: &mut LogLevel, category: &mut Option<LogCategory>) {
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, "Show:"));
+        let level_label = |level: LogLevel| match level {
+            LogLevel::Info => tr(lang, "All messages"),
+            LogLevel::Warn => tr(lang, "Warnings and errors"),
+            LogLevel::Error => tr(lang, "Errors only"),
+        };
+        egui::ComboBox::from_id_source("log_level_filter")
+            .selected_text(level_label(*min_level))
+            .show_ui(ui, |ui| {
+                for level in [LogLevel::Info, LogLevel::Warn, LogLevel::Error] {
+                    ui.selectable_value(min_level, level, level_label(level));
                 }
-                if let Some(ep) = extract_progress {
-                    lang_state.extract_progress = ep;
+            });
+        egui::ComboBox::from_id_source("log_category_filter")
+            .selected_text(category.map_or(tr(lang, "All steps"), |category| tr(lang, category.label())))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(category, None, tr(lang, "All steps"));
+                for option in LogCategory::ALL {
+                    ui.selectable_value(category, Some(option), tr(lang, option.label()));
                 }
-            }
-            drop(app_state); // Release the lock
-            ctx.request_repaint();
-            // Add a small sleep to make the progress visually apparent
-            // This sleep is acceptable as this function runs on a separate thread.
-            std::thread::sleep(std::time::Duration::from_millis(10));
+            });
+    });
+}
+
+fn mirror_config_path() -> Option<PathBuf> {
+    toolchain_config_dir().map(|dir| dir.join("mirrors.toml"))
+}
+
+/// Files under the config directory that a configuration bundle leaves out: the install logs.
+const BUNDLE_EXCLUDED_DIRS: [&str; 1] = ["logs"];
+
+/// Writes every file of the config directory (settings, mirrors, ...) into the zip at `bundle`,
+/// for replicating a setup on another machine. Mirror passwords and tokens stay in the
+/// keychain and are not included. Returns the number of files written.
+fn export_config_bundle(bundle: &Path) -> Result<usize, String> {
+    let config_dir = toolchain_config_dir().ok_or("Could not find the config directory.".to_string())?;
+    let file = File::create(bundle).map_err(|e| format!("Failed to create {}: {}", bundle.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let mut file_count = 0;
+    let entries = WalkDir::new(&config_dir).into_iter()
+        .filter_entry(|entry| entry.depth() != 1 || !BUNDLE_EXCLUDED_DIRS.iter().any(|dir| entry.file_name() == *dir));
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", config_dir.display(), e))?;
+        if !entry.file_type().is_file() {
+            continue;
         }
-    };
+        let relative = entry.path().strip_prefix(&config_dir).unwrap_or(entry.path());
+        let name = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        let contents = fs::read(entry.path()).map_err(|e| format!("Failed to read {}: {}", entry.path().display(), e))?;
+        zip.start_file(name, zip::write::FileOptions::default()).map_err(|e| format!("Failed to write {}: {}", bundle.display(), e))?;
+        zip.write_all(&contents).map_err(|e| format!("Failed to write {}: {}", bundle.display(), e))?;
+        file_count += 1;
+    }
+    zip.finish().map_err(|e| format!("Failed to finish {}: {}", bundle.display(), e))?;
+    Ok(file_count)
+}
 
-    let mut current_log = log_output.lock().expect("Failed to acquire log mutex at start of run_installation_logic");
-    current_log.push_str(&format!("Checking system information...\n"));
-    drop(current_log);
-
-    let (os_name_raw, arch_raw) = detect_platform().ok_or_else(|| {
-        "Current system is not supported.".to_string()
-    })?;
-
-    let mut current_log = log_output.lock().expect("Failed to acquire log mutex after platform detect");
-    current_log.push_str(&format!("OS: {}, ARCH: {}\n", os_name_raw, arch_raw));
-    drop(current_log);
-
-    let install_root = dirs::home_dir().ok_or("Could not find home directory.".to_string())?.join("jdkm");
-    
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(300)) // Increased timeout for potentially larger downloads and installations
-        .build()
-        .map_err(|e| format!("HTTP client creation failed: {}", e))?;
-
-    // Determine download URL and actual version *before* idempotency check
-    let (download_url, _pkg_name, is_zip, actual_download_version) = match vendor {
-        "azul" => {
-            let os_name = os_name_raw;
-            let arch = match arch_raw {
-                "x86_64" => "x64",
-                "aarch64" => "aarch64",
-                _ => arch_raw, // Fallback
-            };
-            let api;
-            let display_version = if install_latest_flag { "latest" } else { version };
-            update_app_state(&ctx, app_state_id, vendor, Some(format!("Preparing Azul Zulu JDK {} installation...", display_version)), None, None);
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Azul start");
-            current_log.push_str(&format!("Preparing Azul Zulu JDK {}...\n", display_version));
-            drop(current_log);
+/// Restores a bundle written by export_config_bundle() into the config directory, overwriting
+/// files of the same name. Returns the number of files restored.
+fn import_config_bundle(bundle: &Path) -> Result<usize, String> {
+    let config_dir = toolchain_config_dir().ok_or("Could not find the config directory.".to_string())?;
+    let file = File::open(bundle).map_err(|e| format!("Failed to open {}: {}", bundle.display(), e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("{} is not a configuration bundle: {}", bundle.display(), e))?;
+    let mut file_count = 0;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("Failed to read {}: {}", bundle.display(), e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        // enclosed_name() rejects absolute paths and `..`, so nothing lands outside the config directory.
+        let relative = entry.enclosed_name().map(Path::to_path_buf)
+            .ok_or_else(|| format!("{} contains an unsafe path: {}", bundle.display(), entry.name()))?;
+        write_archive_entry(&config_dir.join(relative), &mut entry, None)?;
+        file_count += 1;
+    }
+    Ok(file_count)
+}
 
-            if install_latest_flag {
-                api = format!(
-                    "https://api.azul.com/metadata/v1/zulu/packages?latest=true&availability_types=ca&os={}&arch={}&package_type=jdk",
-                    os_name, arch
-                );
-            } else {
-                api = format!(
-                    "https://api.azul.com/metadata/v1/zulu/packages?java_version={}&os={}&arch={}&package_type=jdk&latest=true&availability_types=ca",
-                    version, os_name, arch
-                );
-            }
+/// Loads `mirrors.toml` from the config directory. A missing file means no mirrors.
+fn load_mirror_config() -> Result<MirrorConfig, String> {
+    let Some(path) = mirror_config_path() else {
+        return Ok(MirrorConfig::default());
+    };
+    if !path.exists() {
+        return Ok(MirrorConfig::default());
+    }
+    let contents = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    toml::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
 
-            let resp = client.get(&api)
-                .send().map_err(|e| format!("Azul API call failed: {}", e))?;
-            let json: Value = resp.json().map_err(|e| format!("Failed to parse Azul JSON: {}", e))?;
-
-            let package_info_vec: Vec<&Value> = json.as_array()
-                .ok_or_else(|| "Azul API response is not an array.".to_string())?
-                .iter()
-                .filter(|pkg| {
-                    pkg.get("name")
-                        .and_then(Value::as_str)
-                        .map_or(false, |name| {
-                            name.contains("-jdk") && name.ends_with(".zip")
-                        })
-                })
-                .collect();
-
-            let selected_package = package_info_vec.iter()
-                .find(|&&pkg| {
-                    pkg.get("name")
-                        .and_then(Value::as_str)
-                        .map_or(false, |name| {
-                            !name.contains("crac") && !name.contains("fx")
-                        })
-                })
-                .map(|&pkg| pkg)
-                .or_else(|| {
-                    package_info_vec.iter().find(|&&pkg| {
-                        pkg.get("name")
-                            .and_then(Value::as_str)
-                            .map_or(false, |name| {
-                                !name.contains("crac")
-                            })
-                    })
-                    .map(|&pkg| pkg)
-                })
-                .or_else(|| {
-                    package_info_vec.first().map(|&pkg| pkg)
-                })
-                .ok_or_else(|| "No suitable Azul JDK package (zip) found for the specified criteria.".to_string())?;
-
-            let download_url = selected_package.get("download_url")
-                .and_then(Value::as_str)
-                .map(str::to_string)
-                .ok_or_else(|| "Download link not found in Azul package info".to_string())?;
-
-            let pkg_name_derived = selected_package.get("name")
-                .and_then(Value::as_str)
-                .map(str::to_string)
-                .unwrap_or_else(|| {
-                    download_url.split('/').last()
-                        .unwrap_or("zulu-jdk.zip")
-                        .replace(".zip", "")
-                });
-            
-            let version_from_api = selected_package.get("java_version")
-                .and_then(Value::as_i64)
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| version.to_string()); // Fallback to requested version
+/// Writes `mirrors.toml`, as edited in the Settings window.
+fn save_mirror_config(config: &MirrorConfig) -> Result<(), String> {
+    let path = mirror_config_path().ok_or("Could not find the config directory.".to_string())?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let contents = toml::to_string(config).map_err(|e| format!("Failed to serialize mirrors: {}", e))?;
+    fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
 
-            (download_url, pkg_name_derived, true, version_from_api) // Azul usually provides zips
-        }
+/// Caveats and prerequisites shown at the top of a vendor's tab, for the platform this runs on
+/// (`os` as in `env::consts::OS`). Each note is an English UI string, translated when shown.
+fn vendor_notes(vendor: &str, os: &str) -> Vec<&'static str> {
+    let windows = os == "windows";
+    let mut notes = Vec::new();
+    match vendor {
+        "c_cpp" if windows => notes.push("Installs the fixed MinGW-w64 v11.0.0 release from SourceForge."),
+        "c_cpp" => notes.push("MinGW-w64 is only installed on Windows. On Linux/macOS, use the system compiler (e.g. build-essential, Xcode command line tools or Homebrew gcc)."),
+        "rust" => notes.push("Rust is installed with rustup on the stable channel; use rustup itself to add toolchains and components."),
+        "python" if windows => notes.push("The Windows embeddable Python package has no pip; get-pip.py is downloaded to bootstrap it before libraries are installed."),
+        "erlang" if !windows => notes.push("Precompiled Erlang/OTP builds are only published for 64-bit Windows. On Linux/macOS, use your package manager, kerl or asdf."),
+        "ruby" if windows => notes.push("Ruby is installed with RubyInstaller, without the MSYS2 DevKit; run `ridk install` before installing gems with native extensions."),
+        "ruby" => notes.push("Ruby is only installed on Windows. On Linux/macOS, use rbenv with ruby-build, asdf or your package manager."),
+        "elixir" => notes.push("Elixir needs Erlang/OTP, and the Elixir build is picked to match the installed OTP major version."),
+        "oracle" => notes.push("Oracle JDK is distributed under the Oracle No-Fee Terms and Conditions, which must be accepted below before installing."),
+        "graalvm" => notes.push("GraalVM for JDK 21 and later includes native-image but no gu, so the JavaScript and Python components can only be added to older releases, which install them with the bundled gu."),
+        "openjdk" => notes.push("OpenJDK builds are found by reading jdk.java.net, so a change to that site can break version resolution."),
+        _ => {}
+    }
+    if requires_java(vendor) {
+        notes.push("Requires a JDK; install one from a Java tab first.");
+    }
+    notes
+}
 
-        "temurin" => {
-            let os_name = os_name_raw;
-            let arch = match arch_raw {
-                "x86_64" => "x64",
-                "aarch64" => "aarch64",
-                _ => arch_raw, // Fallback
-            };
-            let api;
-            let display_version = if install_latest_flag { "latest" } else { version };
-            update_app_state(&ctx, app_state_id, vendor, Some(format!("Preparing Temurin JDK {} installation...", display_version)), None, None);
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Temurin start");
-            current_log.push_str(&format!("Preparing Temurin JDK {}...\n", display_version));
-            drop(current_log);
+/// A persisted PATH entry (a shell profile line, or an element of the user PATH in the registry)
+/// that points into `<install root>/*_versions` but targets a missing directory or duplicates an earlier entry.
+#[derive(Clone)]
+struct StalePathEntry {
+    location: String, // Profile file path, or the registry value
+    line_number: Option<usize>, // 0-based line in a shell profile; None for registry entries
+    entry: String,
+    reason: &'static str,
+    replacement: Option<String>, // Profile line to keep with only the missing elements removed; None drops the entry
+}
 
-            if install_latest_flag {
-                api = format!(
-                    "https://api.adoptium.net/v3/assets/latest/all/hotspot?os={}&architecture={}&image_type=jdk",
-                    os_name, arch
-                );
-            } else {
-                api = format!(
-                    "https://api.adoptium.net/v3/assets/latest/{}/hotspot?os={}&architecture={}&image_type=jdk",
-                    version, os_name, arch
-                );
-            }
+/// Returns the install-root paths under a `*_versions` directory mentioned in a PATH element or profile line.
+fn managed_paths_in(text: &str, install_root: &std::path::Path) -> Vec<PathBuf> {
+    let home = dirs::home_dir().map(|h| h.display().to_string()).unwrap_or_default();
+    let expanded = text
+        .replace("${HOME}", &home)
+        .replace("$HOME", &home)
+        .replace("%USERPROFILE%", &home)
+        .replace("~/", &format!("{}/", home));
+    expanded
+        .split(|c: char| c == ':' && !cfg!(windows) || c == ';' || c == '"' || c == '\'' || c == '=' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| {
+            path.starts_with(install_root)
+                && path.components().any(|c| c.as_os_str().to_string_lossy().ends_with("_versions"))
+        })
+        .collect()
+}
 
-            let assets: Vec<TemurinAsset> = client.get(&api)
-                .send().map_err(|e| format!("Temurin API call failed: {}", e))?
-                .json().map_err(|e| format!("Failed to parse Temurin JSON: {}", e))?;
-            let pkg = assets.into_iter().next().ok_or_else(|| "Temurin package not found".to_string())?;
-            
-            let is_zip_file = pkg.binary.package.name.ends_with(".zip");
-            let version_from_api = version.to_string(); // Temurin API doesn't easily give exact version from asset list
-            (pkg.binary.package.link, pkg.binary.package.name, is_zip_file, version_from_api)
+/// Rewrites a profile line without its managed elements whose directories no longer exist, e.g.
+/// `export PATH="/stale/bin:$HOME/.cargo/bin:$PATH"` -> `export PATH="$HOME/.cargo/bin:$PATH"`.
+/// Returns None when the variable would only be assigned itself (or nothing), so the line can go.
+#[cfg(not(windows))]
+fn without_missing_elements(line: &str, install_root: &Path) -> Option<String> {
+    let is_separator = |c: char| c == ':' || c == '"' || c == '\'' || c == '=' || c.is_whitespace();
+    let mut rewritten = String::new();
+    let mut kept_words = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let word_len = rest.find(is_separator).unwrap_or(rest.len());
+        if word_len == 0 {
+            rewritten.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
         }
-
-        "openjdk" => {
-            let os_name = os_name_raw;
-            if install_latest_flag {
-                return Err("Latest version not supported for OpenJDK. Please specify a version number.".to_string());
+        let word = &rest[..word_len];
+        rest = &rest[word_len..];
+        if managed_paths_in(word, install_root).iter().any(|path| !path.exists()) {
+            // Drop the element together with one `:` joining it to a neighbour.
+            if let Some(after) = rest.strip_prefix(':') {
+                rest = after;
+            } else if rewritten.ends_with(':') {
+                rewritten.pop();
             }
-            update_app_state(&ctx, app_state_id, vendor, Some(format!("Preparing OpenJDK {} installation...", version)), None, None);
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for OpenJDK start");
-            current_log.push_str(&format!("Preparing OpenJDK {}...\n", version));
-            drop(current_log);
-            let page = format!("https://jdk.java.net/{}", version);
-            let html = client.get(&page)
-                .send().map_err(|e| format!("Failed to request OpenJDK page: {}", e))?
-                .text().map_err(|e| format!("Failed to read HTML: {}", e))?;
-
-            let document = Html::parse_document(&html);
-            let selector = Selector::parse("a").map_err(|e| format!("Failed to parse selector: {:?}", e))?;
-            let link = document.select(&selector)
-                .filter_map(|a| a.value().attr("href"))
-                .find(|l| l.contains(os_name) && l.ends_with(".zip"))
-                .ok_or_else(|| "OpenJDK ZIP link not found".to_string())?;
-            let pkg_name_derived = link.split('/').last()
-                .unwrap_or("openjdk.zip")
-                .replace(".zip", "");
-            (link.to_string(), pkg_name_derived, true, version.to_string()) // OpenJDK usually provides zips
+        } else {
+            rewritten.push_str(word);
+            kept_words.push(word);
         }
+    }
+    // `export NAME=...` or `set -gx NAME ...`: anything beyond the name and `$NAME` is a real value.
+    let mut words = kept_words.into_iter().skip_while(|word| *word == "export" || *word == "set" || word.starts_with('-'));
+    let name = words.next()?;
+    let self_references = [format!("${}", name), format!("${{{}}}", name)];
+    words.any(|word| !self_references.iter().any(|reference| reference == word)).then_some(rewritten)
+}
 
-        "python" => {
-            let os_name = os_name_raw;
-            let python_version_to_download = if install_latest_flag {
-                update_app_state(&ctx, app_state_id, vendor, Some("Finding latest Python version...".to_string()), None, None);
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Python version search");
-                current_log.push_str("Searching for latest Python 3.x version...\n");
-                drop(current_log);
-                let latest_version = get_latest_python_version()?; // Call the new function
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex after Python version search");
-                current_log.push_str(&format!("Found latest Python version: {}\n", latest_version));
-                drop(current_log);
-                latest_version
-            } else {
-                version.to_string()
-            };
-
-            let (url, is_zip_file) = match os_name {
-                "windows" => {
-                    // Prefer embeddable zip for Windows
-                    (format!("https://www.python.org/ftp/python/{}/python-{}-embed-amd64.zip", python_version_to_download, python_version_to_download), true)
-                },
-                "darwin" | "linux" => { // macOS and Linux
-                    // Prefer gzipped tarball for macOS/Linux
-                    (format!("https://www.python.org/ftp/python/{}/Python-{}.tgz", python_version_to_download, python_version_to_download), false)
-                },
-                _ => return Err(format!("Python installation not supported for OS: {}", os_name)),
-            };
-            
-            let pkg_name_derived = url.split('/').last()
-                .unwrap_or("python_package")
-                .to_string();
-
-            update_app_state(&ctx, app_state_id, vendor, Some(format!("Preparing Python {} installation...", python_version_to_download)), None, None);
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Python start");
-            current_log.push_str(&format!("Preparing Python {}...\n", python_version_to_download));
-            drop(current_log);
-
-            (url, pkg_name_derived, is_zip_file, python_version_to_download) // Pass the actual version to be used for path
-        }
-        "c_cpp" => {
-            let os_name = os_name_raw;
-            update_app_state(&ctx, app_state_id, vendor, Some("Preparing C/C++ (MinGW-w64) installation...".to_string()), None, None);
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for C/C++ start");
-            current_log.push_str("Preparing C/C++ (MinGW-w64)...\n");
-            drop(current_log);
+/// Shell profiles that may carry PATH exports for managed toolchains.
+#[cfg(not(windows))]
+fn shell_profile_candidates() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else { return Vec::new() };
+    [".bashrc", ".bash_profile", ".zshrc", ".profile", ".config/fish/config.fish"]
+        .iter()
+        .map(|name| home.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
 
-            if os_name != "windows" {
-                return Err("C/C++ (MinGW-w64) installation via this installer is only supported on Windows. For Linux/macOS, please use your system's package manager (e.g., for GCC/Clang: `sudo apt install build-essential` on Debian/Ubuntu, `xcode-select --install` / `brew install gcc` on macOS).".to_string());
+/// Scans the persisted PATH (shell profiles on Unix) for broken or duplicated managed entries.
+#[cfg(not(windows))]
+fn scan_stale_path_entries(install_root: &Path) -> Result<Vec<StalePathEntry>, String> {
+    let mut stale = Vec::new();
+    for profile in shell_profile_candidates() {
+        let contents = fs::read_to_string(&profile).map_err(|e| format!("Failed to read {}: {}", profile.display(), e))?;
+        let mut seen_lines = std::collections::HashSet::new();
+        for (line_number, line) in contents.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('#') {
+                continue;
             }
-            // For simplicity, hardcode a common MinGW-w64 build for x64 Windows.
-            // A more robust solution would involve parsing SourceForge or similar.
-            let url = "https://sourceforge.net/projects/mingw-w64/files/mingw-w64/mingw-w64-release/mingw-w64-v11.0.0.zip/download"; // Fixed URL for MinGW-w64 v11.0.0
-            let pkg_name_derived = "mingw-w64-v11.0.0.zip".to_string();
-            let is_zip_file = true;
-            let actual_version = "11.0.0".to_string(); // Placeholder for MinGW version
-
-            (url.to_string(), pkg_name_derived, is_zip_file, actual_version)
-        }
-        "rust" => {
-            let os_name = os_name_raw;
-            update_app_state(&ctx, app_state_id, vendor, Some("Preparing Rust installation...".to_string()), None, None);
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Rust start");
-            current_log.push_str("Preparing Rust via rustup...\n");
-            drop(current_log);
-            let (url, is_zip_file) = match os_name {
-                "windows" => ("https://win.rustup.rs/x86_64".to_string(), false), // rustup-init.exe is not a zip
-                "darwin" | "linux" => ("https://sh.rustup.rs".to_string(), false), // rustup-init.sh is not a zip
-                _ => return Err(format!("Rust installation not supported for OS: {}", os_name)),
-            };
-            let pkg_name_derived = if os_name == "windows" { "rustup-init.exe".to_string() } else { "rustup-init.sh".to_string() };
-            let actual_version = "stable".to_string(); // rustup installs stable by default
-            (url, pkg_name_derived, is_zip_file, actual_version)
-        }
-        "nodejs" => {
-            let os_name = os_name_raw;
-            let arch = match arch_raw {
-                "x86_64" => "x64",
-                "aarch64" => "arm64",
-                _ => arch_raw, // Fallback
+            let managed_paths = managed_paths_in(trimmed, install_root);
+            if managed_paths.is_empty() {
+                continue;
+            }
+            let (reason, replacement) = if !seen_lines.insert(trimmed.to_string()) {
+                (Some("duplicate entry"), None)
+            } else if managed_paths.iter().any(|path| !path.exists()) {
+                (Some("directory no longer exists"), without_missing_elements(line, install_root))
+            } else {
+                (None, None)
             };
-            update_app_state(&ctx, app_state_id, vendor, Some("Preparing Node.js LTS installation...".to_string()), None, None);
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Node.js start");
-            current_log.push_str("Preparing Node.js LTS...\n");
-            drop(current_log);
-
-            let base_url = "https://nodejs.org/dist/latest-lts/";
-            let resp = client.get(base_url)
-                .send().map_err(|e| format!("Failed to reach nodejs.org: {}", e))?
-                .text().map_err(|e| format!("Failed to read nodejs.org HTML: {}", e))?;
-            
-            let document = Html::parse_document(&resp);
-            let selector = Selector::parse("a").map_err(|e| format!("Failed to parse selector for Node.js version: {:?}", e))?;
-
-            let mut node_version = "unknown".to_string();
-            let mut download_link = None;
-            let mut is_zip_file_node = false; // Renamed to avoid conflict
-
-            // Find the latest LTS version link
-            for element in document.select(&selector) {
-                if let Some(href) = element.value().attr("href") {
-                    if href.starts_with("v") && href.ends_with("/") && href.contains("lts") { // Look for LTS versions
-                        node_version = href.trim_start_matches('v').trim_end_matches('/').to_string();
-                        // Now search for the correct file within this version's directory
-                        let expected_filename_part = format!("{}-{}", os_name, arch);
-                        
-                        // Construct the full URL for the specific OS/arch
-                        let full_version_url = format!("{}{}", base_url, href);
-                        let version_resp = client.get(&full_version_url)
-                            .send().map_err(|e| format!("Failed to reach Node.js version page: {}", e))?
-                            .text().map_err(|e| format!("Failed to read Node.js version HTML: {}", e))?;
-                        let version_document = Html::parse_document(&version_resp);
-                        let version_selector = Selector::parse("a").map_err(|e| format!("Failed to parse selector for Node.js file: {:?}", e))?;
-
-                        for file_element in version_document.select(&version_selector) {
-                            if let Some(file_href) = file_element.value().attr("href") {
-                                if file_href.contains(&expected_filename_part) {
-                                    if os_name == "windows" && file_href.ends_with(".zip") {
-                                        download_link = Some(format!("{}{}", full_version_url, file_href));
-                                        is_zip_file_node = true;
-                                        break;
-                                    } else if (os_name == "darwin" || os_name == "linux") && (file_href.ends_with(".tar.gz") || file_href.ends_with(".tar.xz")) {
-                                        download_link = Some(format!("{}{}", full_version_url, file_href));
-                                        is_zip_file_node = false;
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                        if download_link.is_some() {
-                            break; // Found the download link for the latest LTS
-                        }
-                    }
-                }
+            if let Some(reason) = reason {
+                stale.push(StalePathEntry {
+                    location: profile.display().to_string(),
+                    line_number: Some(line_number),
+                    entry: line.to_string(),
+                    reason,
+                    replacement,
+                });
             }
-
-            let final_download_url = download_link.ok_or_else(|| {
-                format!("Could not find Node.js LTS download for {}/{}", os_name, arch)
-            })?;
-            let pkg_name_derived = final_download_url.split('/').last().unwrap_or("nodejs_package").to_string();
-            (final_download_url, pkg_name_derived, is_zip_file_node, node_version)
         }
-        "go" => {
-            let os_name = os_name_raw;
-            update_app_state(&ctx, app_state_id, vendor, Some("Preparing Go installation...".to_string()), None, None);
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Go start");
-            current_log.push_str("Preparing Go...\n");
-            drop(current_log);
-
-            let (download_url_go, pkg_name_go, is_zip_go) = get_latest_go_version(os_name, arch_raw)?;
-            let actual_version_go = pkg_name_go.split('.').next().unwrap_or("unknown").trim_start_matches("go").to_string();
+    }
+    Ok(stale)
+}
 
-            (download_url_go, pkg_name_go, is_zip_go, actual_version_go)
+/// Removes the given stale profile lines, or only their missing elements where the rest of the line
+/// still matters (see without_missing_elements()), returning one log line per change.
+#[cfg(not(windows))]
+fn remove_stale_path_entries(entries: &[StalePathEntry]) -> Result<Vec<String>, String> {
+    let mut removals = Vec::new();
+    let mut by_file: HashMap<&str, Vec<&StalePathEntry>> = HashMap::new();
+    for entry in entries {
+        by_file.entry(entry.location.as_str()).or_default().push(entry);
+    }
+    for (file, file_entries) in by_file {
+        let contents = fs::read_to_string(file).map_err(|e| format!("Failed to read {}: {}", file, e))?;
+        let lines: Vec<&str> = contents.lines().collect();
+        // Only touch lines that still match what was scanned, in case the file changed in between.
+        let changed_lines: HashMap<usize, &StalePathEntry> = file_entries.iter()
+            .filter_map(|entry| entry.line_number.filter(|n| lines.get(*n) == Some(&entry.entry.as_str())).map(|n| (n, *entry)))
+            .collect();
+        let mut kept: Vec<&str> = lines.iter().enumerate()
+            .filter_map(|(n, line)| match changed_lines.get(&n) {
+                Some(entry) => entry.replacement.as_deref(),
+                None => Some(*line),
+            })
+            .collect();
+        if contents.ends_with('\n') {
+            kept.push("");
         }
-        other => {
-            return Err(format!("Unsupported vendor: {}", other));
+        fs::write(file, kept.join("\n")).map_err(|e| format!("Failed to write {}: {}", file, e))?;
+        for entry in file_entries.iter().filter(|entry| entry.line_number.is_some_and(|n| changed_lines.contains_key(&n))) {
+            removals.push(match &entry.replacement {
+                Some(replacement) => format!("Rewrote in {} ({}): {} -> {}", file, entry.reason, entry.entry.trim(), replacement.trim()),
+                None => format!("Removed from {} ({}): {}", file, entry.reason, entry.entry.trim()),
+            });
         }
-    };
-
-    // Determine the expected final installation path for idempotency check
-    let expected_final_sdk_path = if vendor == "rust" {
-        dirs::home_dir().ok_or_else(|| "Could not find home directory for .cargo path.".to_string())?.join(".cargo")
-    } else {
-        install_root.join(format!("{}_versions", vendor)).join(format!("{}-{}", vendor, actual_download_version))
-    };
+    }
+    Ok(removals)
+}
 
-    // --- Idempotency Check ---
-    update_app_state(&ctx, app_state_id, vendor, Some("Checking for existing installations...".to_string()), None, None);
-    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for existing installations check");
-    
-    let mut is_already_installed = false;
-    if expected_final_sdk_path.exists() {
-        let (verification_command_path, version_arg) = match vendor {
-            "python" => (expected_final_sdk_path.join(if os_name_raw == "windows" { "python.exe" } else { "bin/python3" }), "--version"),
-            "c_cpp" => (expected_final_sdk_path.join(if os_name_raw == "windows" { "bin/gcc.exe" } else { "bin/gcc" }), "--version"),
-            "rust" => (expected_final_sdk_path.join("bin/rustc"), "--version"), // .cargo/bin/rustc
-            "nodejs" => (expected_final_sdk_path.join(if os_name_raw == "windows" { "node.exe" } else { "bin/node" }), "--version"),
-            "go" => (expected_final_sdk_path.join(if os_name_raw == "windows" { "bin/go.exe" } else { "bin/go" }), "version"),
-            _ => (expected_final_sdk_path.join(if os_name_raw == "windows" { "bin/java.exe" } else { "bin/java" }), "-version"), // Java
+/// Scans the persisted user PATH (`HKCU\Environment\Path`) for broken or duplicated managed entries.
+#[cfg(windows)]
+fn scan_stale_path_entries(install_root: &Path) -> Result<Vec<StalePathEntry>, String> {
+    let user_path = read_user_path_from_registry()?;
+    let mut seen = std::collections::HashSet::new();
+    let mut stale = Vec::new();
+    for element in user_path.split(';').filter(|element| !element.is_empty()) {
+        let managed_paths = managed_paths_in(element, install_root);
+        if managed_paths.is_empty() {
+            continue;
+        }
+        let reason = if !seen.insert(element.to_lowercase()) {
+            Some("duplicate entry")
+        } else if managed_paths.iter().any(|path| !path.exists()) {
+            Some("directory no longer exists")
+        } else {
+            None
         };
+        if let Some(reason) = reason {
+            stale.push(StalePathEntry {
+                location: "HKCU\\Environment\\Path".to_string(),
+                line_number: None,
+                entry: element.to_string(),
+                reason,
+                replacement: None,
+            });
+        }
+    }
+    Ok(stale)
+}
 
-        if verification_command_path.exists() {
-            let output = Command::new(&verification_command_path)
-                .arg(version_arg)
-                .output();
-            
-            if let Ok(output) = output {
-                let installed_version_str = if vendor == "python" {
-                    String::from_utf8_lossy(&output.stdout).trim().replace("Python ", "").to_string()
-                } else if vendor == "rust" {
-                    String::from_utf8_lossy(&output.stdout).lines().next()
-                        .unwrap_or("unknown rustc version").replace("rustc ", "").split(' ').next().unwrap_or("unknown").to_string()
-                }
-                else if vendor == "c_cpp" {
-                    String::from_utf8_lossy(&output.stdout).lines().next()
-                        .unwrap_or("unknown gcc version").split(' ').nth(2).unwrap_or("unknown").to_string()
-                }
-                else if vendor == "nodejs" {
-                    String::from_utf8_lossy(&output.stdout).trim().replace("v", "").to_string()
-                }
-                else if vendor == "go" {
-                    String::from_utf8_lossy(&output.stdout).trim().replace("go version go", "").split_whitespace().next().unwrap_or("unknown").to_string()
-                }
-                else { // Java vendors
-                    let stderr_str = String::from_utf8_lossy(&output.stderr);
-                    stderr_str.lines().find(|line| line.contains("version"))
-                        .map(|line| line.replace("openjdk version \"", "").replace("java version \"", "").trim_end_matches('"').to_string())
-                        .unwrap_or_else(|| "unknown".to_string())
-                };
-
-                // Compare installed version with requested version/latest logic
-                let target_version_for_check = if install_latest_flag {
-                    actual_download_version.clone() // Check against the version we *would* download
-                } else {
-                    version.to_string() // Check against the explicitly requested version
-                };
+/// Removes the given stale elements from the user PATH, returning one log line per removal.
+#[cfg(windows)]
+fn remove_stale_path_entries(entries: &[StalePathEntry]) -> Result<Vec<String>, String> {
+    let user_path = read_user_path_from_registry()?;
+    let mut removals = Vec::new();
+    let mut kept: Vec<&str> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for element in user_path.split(';').filter(|element| !element.is_empty()) {
+        let first_occurrence = seen.insert(element.to_lowercase());
+        // A duplicate entry removes the later copies only; a broken one removes every copy.
+        let stale = entries.iter().find(|entry| {
+            entry.entry.eq_ignore_ascii_case(element)
+                && (entry.reason != "duplicate entry" || !first_occurrence)
+        });
+        match stale {
+            Some(entry) => removals.push(format!("Removed from {} ({}): {}", entry.location, entry.reason, element)),
+            None => kept.push(element),
+        }
+    }
+    write_user_path_to_registry(&kept.join(";"))?;
+    broadcast_environment_change();
+    Ok(removals)
+}
 
-                if is_version_compatible(&installed_version_str, &target_version_for_check) {
-                    current_log.push_str(&format!("{} version {} is already installed at {}.\n", vendor, installed_version_str, expected_final_sdk_path.display()));
-                    is_already_installed = true;
-                } else {
-                    current_log.push_str(&format!("Existing {} version {} at {} is not compatible with requested version {}. Proceeding with new installation.\n", vendor, installed_version_str, expected_final_sdk_path.display(), target_version_for_check));
-                }
-            } else {
-                current_log.push_str(&format!("Failed to verify existing {} installation at {}. Proceeding with new installation.\n", vendor, expected_final_sdk_path.display()));
+/// Runs the integrity audit for `vendor`'s last install (or its `current`/newest install when
+/// nothing was installed this session) and formats the result for the tab's log.
+fn run_integrity_check(locations: &InstallLocations, vendor: &str, last_install: Option<PathBuf>) -> (LogLevel, String) {
+    let Some(sdk_root) = last_install
+        .or_else(|| current_version_dir(locations, vendor))
+        .or_else(|| installed_versions(locations, vendor).into_iter().next().map(|(_, path)| path)) else {
+        return (LogLevel::Error, format!("No {} installation found under {}\n", vendor, locations.root.display()));
+    };
+    let report = match verify_integrity_manifest(&sdk_root) {
+        Ok(report) => report,
+        Err(e) => return (LogLevel::Error, format!("Integrity check failed: {}\n", e)),
+    };
+    let mut message = format!("Integrity check of {}:\n", sdk_root.display());
+    if report.changed.is_empty() && report.missing.is_empty() && report.added.is_empty() {
+        message.push_str("All files match the manifest recorded at install time.\n");
+    }
+    for (label, paths) in [("Changed", &report.changed), ("Missing", &report.missing), ("Added", &report.added)] {
+        if !paths.is_empty() {
+            message.push_str(&format!("{} ({}):\n", label, paths.len()));
+            for path in paths {
+                message.push_str(&format!("  {}\n", path));
             }
-        } else {
-            current_log.push_str(&format!("Executable not found for existing {} installation at {}. Proceeding with new installation.\n", vendor, expected_final_sdk_path.display()));
         }
-    } else {
-        current_log.push_str(&format!("No existing {} installation found at {}. Proceeding with new installation.\n", vendor, expected_final_sdk_path.display()));
-    }
-    drop(current_log);
-
-    if is_already_installed {
-        update_app_state(&ctx, app_state_id, vendor, Some(format!("{} is already installed.", vendor)), Some(1.0), Some(1.0));
-        return Ok(());
-    }
-    // --- End Idempotency Check ---
-
-    // Proceed with download and installation if not already installed
-    update_app_state(&ctx, app_state_id, vendor, Some(format!("Downloading {}...", vendor)), Some(0.0), Some(0.0));
-    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for download start");
-    current_log.push_str(&format!("Downloading: {}\n", download_url));
-    drop(current_log);
-    
-    let mut response = client.get(&download_url)
-        .send().map_err(|e| format!("Failed to download from {}: {}", download_url, e))?;
-
-    let total_size = response.content_length().unwrap_or(0);
-    let mut downloaded_bytes: u64 = 0;
-    let mut buffer = Vec::new(); // Use a buffer to accumulate bytes
-
-    // Read the response body in chunks and update progress
-    loop {
-        if cancel_requested.load(Ordering::SeqCst) {
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for cancellation during download");
-            current_log.push_str("Installation cancelled during download.\n");
-            drop(current_log);
-            update_app_state(&ctx, app_state_id, vendor, Some("Installation cancelled.".to_string()), None, None);
-            return Err("Installation cancelled by user.".to_string());
-        }
-        let mut chunk = vec![0; 8192]; // Read in 8KB chunks
-        let bytes_read = match response.read(&mut chunk) {
-            Ok(0) => break, // End of stream
-            Ok(n) => n,
-            Err(e) => return Err(format!("Failed to read download stream: {}", e)),
-        };
-        buffer.extend(chunk.iter().take(bytes_read));
-        downloaded_bytes += bytes_read as u64;
-
-        let progress = if total_size > 0 {
-            downloaded_bytes as f32 / total_size as f32
-        } else {
-            0.0
-        };
-        update_app_state(&ctx, app_state_id, vendor, Some(format!("Downloading... {:.0}%", progress * 100.0)), Some(progress), None);
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for download progress");
-        current_log.push_str(&format!("Download progress: {:.2}%\n", progress * 100.0));
-        drop(current_log);
     }
+    let level = if report.changed.is_empty() && report.missing.is_empty() && report.added.is_empty() { LogLevel::Info } else { LogLevel::Warn };
+    (level, message)
+}
 
-    let mut bytes_cursor = Cursor::new(buffer);
+/// Represents the runtime state of a specific language installation.
+struct LanguageState {
+    output_log: Arc<Mutex<InstallLog>>, // Shared state for logging
+    is_installing: bool,
+    install_result: Option<Result<(), String>>,
+    download_progress: f32, // 0.0 to 1.0
+    extract_progress: f32,  // 0.0 to 1.0
+    post_install_progress: f32, // 0.0 to 1.0, e.g. Python libraries installed / requested
+    current_status: String,
+    cancel_requested: Arc<AtomicBool>,
+    install_lock: Arc<Mutex<()>>, // Held by the worker for the whole install, so only one writes to the vendor's directory
+    last_install: Option<InstalledSdk>, // Most recent successful install, for "Export SBOM"
+    last_resolved_url: Option<String>, // Download URL of the most recent resolution (after mirrors), for "Copy URL"
+    installed_scan: Option<SharedInstalledScan>, // "Installed versions" section; None until the tab is first shown
+}
 
-    // Create the base directory for versions if it doesn't exist
-    let vendor_versions_path = install_root.join(format!("{}_versions", vendor));
-    fs::create_dir_all(&vendor_versions_path).map_err(|e| format!("Failed to create vendor versions directory {}: {}", vendor_versions_path.display(), e))?;
+/// One row of a tab's "Installed versions" section.
+struct InstalledVersionRow {
+    version: String, // From the directory name
+    path: PathBuf,
+    reported: Option<String>, // What the executable reports; None if it couldn't be run
+    active: bool, // The `current` link points here
+}
 
-    let mut extracted_top_level_dir_name: Option<String> = None;
-    let current_install_target_path = expected_final_sdk_path.clone(); // Use the pre-determined path
+/// Installed versions of one vendor, filled in by the scan thread; `None` while scanning.
+type SharedInstalledScan = Arc<Mutex<Option<Vec<InstalledVersionRow>>>>;
+
+/// Lists `vendor`'s installs, running each one's verification command. Slow; call off the UI thread.
+fn scan_installed_versions(locations: &InstallLocations, vendor: &str) -> Vec<InstalledVersionRow> {
+    let active_dir = current_version_dir(locations, vendor).and_then(|dir| fs::canonicalize(dir).ok());
+    installed_versions(locations, vendor).into_iter()
+        .map(|(version, path)| InstalledVersionRow {
+            reported: installed_sdk_version(vendor, &path),
+            active: active_dir.is_some() && fs::canonicalize(&path).ok() == active_dir,
+            version,
+            path,
+        })
+        .collect()
+}
 
-    if vendor == "rust" {
-        // Rustup handles its own installation path, typically ~/.cargo
-        // We just need to execute the downloaded rustup-init.
-        let rustup_init_path = if os_name_raw == "windows" {
-            install_root.join("rustup-init.exe") // Place init in jdkm root for temp use
-        } else {
-            install_root.join("rustup-init.sh")
-        };
+/// Scans `vendor`'s versions directory on a background thread, so the verification commands
+/// don't run on every frame.
+fn start_installed_scan(locations: &InstallLocations, vendor: &str, ctx: &egui::Context) -> SharedInstalledScan {
+    let scan = Arc::new(Mutex::new(None));
+    let scan_clone = scan.clone();
+    let locations = locations.clone();
+    let vendor = vendor.to_string();
+    let ctx_clone = ctx.clone();
+    std::thread::spawn(move || {
+        let versions = scan_installed_versions(&locations, &vendor);
+        *scan_clone.lock().expect("Failed to acquire installed scan mutex") = Some(versions);
+        ctx_clone.request_repaint();
+    });
+    scan
+}
 
-        let mut rustup_file = File::create(&rustup_init_path)
-            .map_err(|e| format!("Failed to create rustup-init file: {}", e))?;
-        io::copy(&mut bytes_cursor, &mut rustup_file)
-            .map_err(|e| format!("Failed to write rustup-init file: {}", e))?;
-        
-        if os_name_raw != "windows" {
-            Command::new("chmod")
-                .arg("+x")
-                .arg(&rustup_init_path)
-                .output()
-                .map_err(|e| format!("Failed to make rustup-init.sh executable: {}", e))?;
-        }
+impl LanguageState {
+    /// Resets the tab's progress and log for a new installation run of `vendor`.
+    fn begin_install(&mut self, lang: Language, vendor: &str, max_log_kib: usize) {
+        self.output_log.lock().expect("Failed to acquire log mutex to clear log").restart(vendor, max_log_kib * 1024);
+        self.is_installing = true;
+        self.install_result = None;
+        self.download_progress = 0.0;
+        self.extract_progress = 0.0;
+        self.post_install_progress = 0.0;
+        self.current_status = tr(lang, "Starting installation process...").to_string();
+        self.cancel_requested.store(false, Ordering::SeqCst);
+    }
 
-        update_app_state(&ctx, app_state_id, vendor, Some("Running rustup installer...".to_string()), None, Some(0.0));
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for rustup-init run");
-        current_log.push_str("Running rustup-init...\n");
-        drop(current_log);
+    /// Asks the tab's running installation to stop and shows it as cancelled right away.
+    fn cancel(&mut self, lang: Language) {
+        self.cancel_requested.store(true, Ordering::SeqCst);
+        self.is_installing = false;
+        self.install_result = Some(Err("Installation cancelled.".to_string()));
+        self.output_log.lock().expect("Failed to acquire log mutex to clear cancel log").clear();
+        self.download_progress = 0.0;
+        self.extract_progress = 0.0;
+        self.post_install_progress = 0.0;
+        self.current_status = tr(lang, "Installation cancelled.").to_string();
+    }
 
-        let mut command = Command::new(&rustup_init_path);
-        command.arg("--default-toolchain").arg("stable").arg("-y");
-        
-        let rustup_output = command
-            .output()
-            .map_err(|e| format!("Failed to run rustup-init: {}", e))?;
-
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for rustup-init output");
-        current_log.push_str(&format!("{}", String::from_utf8_lossy(&rustup_output.stdout)));
-        current_log.push_str(&format!("{}", String::from_utf8_lossy(&rustup_output.stderr)));
-        drop(current_log);
-
-        if rustup_output.status.success() {
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Rust success");
-            current_log.push_str("Rust installed successfully via rustup.\n");
-            drop(current_log);
-            // The actual_sdk_root for Rust is ~/.cargo, which was already set in expected_final_sdk_path
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Rust cargo home info");
-            current_log.push_str(&format!("Rust's cargo home: {}\n", expected_final_sdk_path.display()));
-            current_log.push_str(&format!("Rust's PATH has been automatically configured by rustup for persistent use in new terminal sessions.\n"));
-            drop(current_log);
-        } else {
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Rust failure");
-            current_log.push_str("Rust installation failed.\n");
-            drop(current_log);
-            return Err("Rust installation failed.".to_string());
+    /// True while installing with neither progress bar advancing (both at 0 or 1), i.e. during
+    /// version resolution, installer runs and library installs, which report no progress.
+    fn is_progress_indeterminate(&self) -> bool {
+        self.is_installing && [self.download_progress, self.extract_progress].iter().all(|progress| *progress <= 0.0 || *progress >= 1.0)
+    }
+}
+
+impl Default for LanguageState {
+    fn default() -> Self {
+        LanguageState {
+            output_log: Arc::new(Mutex::new(InstallLog::new())),
+            is_installing: false,
+            install_result: None,
+            download_progress: 0.0,
+            extract_progress: 0.0,
+            post_install_progress: 0.0,
+            current_status: "Ready for installation".to_string(),
+            cancel_requested: Arc::new(AtomicBool::new(false)),
+            install_lock: Arc::new(Mutex::new(())),
+            last_install: None,
+            last_resolved_url: None,
+            installed_scan: None,
         }
+    }
+}
 
-        fs::remove_file(&rustup_init_path)
-            .map_err(|e| format!("Failed to remove rustup-init: {}", e))?;
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for rustup-init cleanup");
-        current_log.push_str("Cleaned up rustup-init.\n");
-        drop(current_log);
-
-    } else { // Handle ZIP and Tarball extractions for other vendors
-        if is_zip {
-            let mut archive = ZipArchive::new(bytes_cursor)
-                .map_err(|e| format!("Failed to parse ZIP archive: {}", e))?;
-            let total_files = archive.len();
-            update_app_state(&ctx, app_state_id, vendor, Some("Extracting files, almost there...".to_string()), None, Some(0.0));
-
-            for i in 0..total_files {
-                if cancel_requested.load(Ordering::SeqCst) {
-                    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for cancellation during extraction");
-                    current_log.push_str("Installation cancelled during extraction.\n");
-                    drop(current_log);
-                    update_app_state(&ctx, app_state_id, vendor, Some("Installation cancelled.".to_string()), None, None);
-                    return Err("Installation cancelled by user.".to_string());
-                }
-                let mut file = archive.by_index(i).map_err(|e| format!("Failed to get file from archive at index {}: {}", i, e))?;
-                let file_path_in_zip = PathBuf::from(file.name());
+/// Shows an installation's progress on the vendor's tab, through the app state stored in `ctx`.
+struct TabProgress {
+    ctx: egui::Context,
+    app_state_id: egui::Id,
+    vendor: String,
+    log: Arc<Mutex<InstallLog>>, // The tab's log, shared with the UI
+}
 
-                if extracted_top_level_dir_name.is_none() && file.is_dir() {
-                    if let Some(top_level_component) = file_path_in_zip.components().next().and_then(|c| c.as_os_str().to_str()) {
-                        extracted_top_level_dir_name = Some(top_level_component.to_string());
-                    }
-                }
+impl TabProgress {
+    /// Applies `update` to the tab's state and requests a repaint.
+    fn update_tab(&self, update: impl FnOnce(Language, &mut LanguageState)) {
+        if let Some(app_state_arc) = self.ctx.data(|d| d.get_temp::<Arc<Mutex<JdkInstallerApp>>>(self.app_state_id)) {
+            let mut app_state = app_state_arc.lock().expect("Failed to acquire app state lock to report progress");
+            let language = app_state.settings.language;
+            if let Some(lang_state) = app_state.language_states.get_mut(&self.vendor) {
+                update(language, lang_state);
+            }
+            drop(app_state); // Release the lock
+            self.ctx.request_repaint();
+        }
+    }
+}
 
-                let out_path = current_install_target_path.join(file.name());
+impl ProgressSink for TabProgress {
+    fn set_status(&self, status: &Status) {
+        self.update_tab(|language, lang_state| lang_state.current_status = translate_status(language, status));
+    }
 
-                if (*file.name()).ends_with('/') {
-                    fs::create_dir_all(&out_path).map_err(|e| format!("Failed to create directory {}: {}", out_path.display(), e))?;
-                } else {
-                    if let Some(p) = out_path.parent() {
-                        fs::create_dir_all(p).map_err(|e| format!("Failed to create parent directory {}: {}", p.display(), e))?;
-                    }
-                    let mut outfile = File::create(&out_path).map_err(|e| format!("Failed to create file {}: {}", out_path.display(), e))?;
-                    io::copy(&mut file, &mut outfile).map_err(|e| format!("Failed to copy data to file {}: {}", out_path.display(), e))?;
-                }
-                let progress = (i + 1) as f32 / total_files as f32;
-                update_app_state(&ctx, app_state_id, vendor, Some(format!("Extracting... {:.0}%", progress * 100.0)), None, Some(progress));
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for extraction progress");
-                current_log.push_str(&format!("Extraction progress: {:.2}%\n", progress * 100.0));
-                drop(current_log);
-            }
-        } else { // Handle tarballs (.tgz, .tar.xz)
-            let decoder: Box<dyn Read> = if _pkg_name.ends_with(".tgz") || _pkg_name.ends_with(".tar.gz") {
-                Box::new(GzDecoder::new(bytes_cursor))
-            } else if _pkg_name.ends_with(".tar.xz") {
-                Box::new(XzDecoder::new(bytes_cursor))
-            } else {
-                return Err(format!("Unsupported archive format: {}", _pkg_name));
-            };
+    fn set_download_progress(&self, progress: f32) {
+        self.update_tab(|_, lang_state| lang_state.download_progress = progress);
+    }
 
-            let mut archive = Archive::new(decoder);
-            
-            let mut entries_processed = 0;
-            // A rough estimate for total entries in a tarball for progress, or could count first pass
-            // For now, using a large number to ensure progress bar moves.
-            let total_tar_entries_estimate = 1000.0; 
-            update_app_state(&ctx, app_state_id, vendor, Some("Extracting files, almost there...".to_string()), None, Some(0.0));
-
-            for entry_result in archive.entries().map_err(|e| format!("Failed to read tar archive entries: {}", e))? {
-                if cancel_requested.load(Ordering::SeqCst) {
-                    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for cancellation during tar extraction");
-                    current_log.push_str("Installation cancelled during extraction.\n");
-                    drop(current_log);
-                    update_app_state(&ctx, app_state_id, vendor, Some("Installation cancelled.".to_string()), None, None);
-                    return Err("Installation cancelled by user.".to_string());
-                }
-                let mut entry = entry_result.map_err(|e| format!("Failed to get tar entry: {}", e))?;
-                let entry_path = entry.path().map_err(|e| format!("Failed to get tar entry path: {}", e))?;
+    fn set_extract_progress(&self, progress: f32) {
+        self.update_tab(|_, lang_state| lang_state.extract_progress = progress);
+    }
 
-                if extracted_top_level_dir_name.is_none() && entry.header().entry_type().is_dir() {
-                    if let Some(top_level_component) = entry_path.components().next().and_then(|c| c.as_os_str().to_str()) {
-                        extracted_top_level_dir_name = Some(top_level_component.to_string());
-                    }
-                }
-                
-                let out_path = current_install_target_path.join(&entry_path);
+    fn set_post_install_progress(&self, status: &Status, progress: f32) {
+        self.update_tab(|language, lang_state| {
+            lang_state.current_status = translate_status(language, status);
+            lang_state.post_install_progress = progress;
+        });
+    }
 
-                if entry.header().entry_type().is_dir() {
-                    fs::create_dir_all(&out_path).map_err(|e| format!("Failed to create directory {}: {}", out_path.display(), e))?;
-                } else {
-                    if let Some(p) = out_path.parent() {
-                        fs::create_dir_all(p).map_err(|e| format!("Failed to create parent directory {}: {}", p.display(), e))?;
-                    }
-                    let mut outfile = File::create(&out_path).map_err(|e| format!("Failed to create file {}: {}", out_path.display(), e))?;
-                    io::copy(&mut entry, &mut outfile).map_err(|e| format!("Failed to copy data to file {}: {}", out_path.display(), e))?;
-                }
-                entries_processed += 1;
-                let progress = (entries_processed as f32 / total_tar_entries_estimate).min(1.0);
-                update_app_state(&ctx, app_state_id, vendor, Some(format!("Extracting... {:.0}%", progress * 100.0)), None, Some(progress));
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for tar extraction progress");
-                current_log.push_str(&format!("Extraction progress: {:.2}%\n", progress * 100.0));
-                drop(current_log);
-            }
-            update_app_state(&ctx, app_state_id, vendor, None, None, Some(1.0));
-        }
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex after extraction");
-        current_log.push_str("Extraction complete.\n");
-        drop(current_log);
-
-        // For non-rust installations, the actual SDK root is the current_install_target_path
-        // which now contains the extracted content.
-        // If there was a top-level directory in the archive, append it.
-        if let Some(dir_name) = extracted_top_level_dir_name {
-            // If the extracted content is within a single top-level directory,
-            // move the contents of that directory up to `current_install_target_path`
-            // and then remove the now-empty top-level directory.
-            let temp_extracted_path = current_install_target_path.join(&dir_name);
-            if temp_extracted_path.exists() && temp_extracted_path.is_dir() {
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for dir move");
-                current_log.push_str(&format!("Moving contents from {} to {}...\n", temp_extracted_path.display(), current_install_target_path.display()));
-                drop(current_log);
-
-                // Move contents
-                for entry in fs::read_dir(&temp_extracted_path).map_err(|e| format!("Failed to read temp extracted dir: {}", e))? {
-                    let entry = entry.map_err(|e| format!("Failed to read entry in temp extracted dir: {}", e))?;
-                    let original_path = entry.path();
-                    let target_path = current_install_target_path.join(entry.file_name());
-                    fs::rename(&original_path, &target_path).map_err(|e| format!("Failed to move {:?} to {:?}: {}", original_path, target_path, e))?;
-                }
-                // Remove the empty top-level directory
-                fs::remove_dir(&temp_extracted_path).map_err(|e| format!("Failed to remove temp extracted dir {}: {}", temp_extracted_path.display(), e))?;
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for dir move complete");
-                current_log.push_str("Contents moved.\n");
-                drop(current_log);
-            }
-        }
+    fn set_resolved_url(&self, url: &str) {
+        self.update_tab(|_, lang_state| lang_state.last_resolved_url = Some(url.to_string()));
     }
 
+    fn log_entry(&self, level: LogLevel, category: LogCategory, text: &str) {
+        self.log.lock().expect("Failed to acquire log mutex for install log").push(level, category, text);
+    }
+}
 
-    // Set JAVA_HOME or PYTHON_HOME or PATH for C/C++/Rust/Node.js/Go
-    // Use expected_final_sdk_path as the actual_sdk_root after successful installation
-    let actual_sdk_root_final = expected_final_sdk_path;
-
-    if vendor == "python" {
-        std::env::set_var("PYTHON_HOME", &actual_sdk_root_final);
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for PYTHON_HOME set");
-        current_log.push_str(&format!("PYTHON_HOME={}\n", actual_sdk_root_final.display()));
-        current_log.push_str(&format!("For persistent use across new terminal sessions, you will need to manually add `{}` to your system's PATH environment variable. This typically requires administrative privileges.\n", actual_sdk_root_final.display()));
-        drop(current_log);
-    } else if vendor == "c_cpp" {
-        let mingw_bin_path = actual_sdk_root_final.join("bin");
-        let current_path = env::var("PATH").unwrap_or_default();
-        env::set_var("PATH", format!("{};{}", mingw_bin_path.display(), current_path));
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for C/C++ PATH update");
-        current_log.push_str(&format!("PATH updated for current session: {}\n", mingw_bin_path.display()));
-        current_log.push_str(&format!("For persistent use across new terminal sessions, you will need to manually add `{}` to your system's PATH environment variable. This typically requires administrative privileges.\n", mingw_bin_path.display()));
-        drop(current_log);
-    } else if vendor == "nodejs" {
-        let node_bin_path = if os_name_raw == "windows" {
-            actual_sdk_root_final.clone() // Node.js on Windows has node.exe directly in root
-        } else {
-            actual_sdk_root_final.join("bin")
-        };
-        let current_path = env::var("PATH").unwrap_or_default();
-        env::set_var("PATH", format!("{};{}", node_bin_path.display(), current_path));
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Node.js PATH update");
-        current_log.push_str(&format!("PATH updated for current session: {}\n", node_bin_path.display()));
-        current_log.push_str(&format!("For persistent use across new terminal sessions, you will need to manually add `{}` to your system's PATH environment variable. This typically requires administrative privileges.\n", node_bin_path.display()));
-        drop(current_log);
-    } else if vendor == "go" {
-        std::env::set_var("GOROOT", &actual_sdk_root_final);
-        let go_bin_path = actual_sdk_root_final.join("bin");
-        let current_path = env::var("PATH").unwrap_or_default();
-        env::set_var("PATH", format!("{};{}", go_bin_path.display(), current_path));
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Go GOROOT/PATH update");
-        current_log.push_str(&format!("GOROOT={}\n", actual_sdk_root_final.display()));
-        current_log.push_str(&format!("PATH updated for current session: {}\n", go_bin_path.display()));
-        current_log.push_str(&format!("For persistent use across new terminal sessions, you will need to manually add `{}` to your system's PATH environment variable. This typically requires administrative privileges.\n", go_bin_path.display()));
-        drop(current_log);
-    }
-    else if vendor != "rust" { // Java vendors
-        std::env::set_var("JAVA_HOME", &actual_sdk_root_final);
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for JAVA_HOME set");
-        current_log.push_str(&format!("JAVA_HOME={}\n", actual_sdk_root_final.display()));
-        current_log.push_str(&format!("For persistent use across new terminal sessions, you will need to manually add `{}` to your system's PATH environment variable. This typically requires administrative privileges.\n", actual_sdk_root_final.join("bin").display()));
-        drop(current_log);
-    }
-
-
-    // Verification step
-    update_app_state(&ctx, app_state_id, vendor, Some(format!("Verifying {} installation...", vendor)), None, None);
-    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for verification start");
-    current_log.push_str(&format!("Verifying {} version...\n", vendor));
-    drop(current_log);
-
-    let (verification_command_path, version_arg) = match vendor {
-        "python" => {
-            let path = if os_name_raw == "windows" {
-                actual_sdk_root_final.join("python.exe")
-            } else {
-                actual_sdk_root_final.join("bin").join("python3")
-            };
-            (path, "--version")
-        },
-        "c_cpp" => {
-            let path = if os_name_raw == "windows" {
-                actual_sdk_root_final.join("bin").join("gcc.exe")
-            } else {
-                actual_sdk_root_final.join("bin").join("gcc")
-            };
-            (path, "--version")
-        },
-        "rust" => {
-            let path = dirs::home_dir().ok_or_else(|| "Could not find home directory for .cargo path.".to_string())?.join(".cargo").join("bin").join("rustc");
-            (path, "--version")
-        },
-        "nodejs" => {
-            let path = if os_name_raw == "windows" {
-                actual_sdk_root_final.join("node.exe")
-            } else {
-                actual_sdk_root_final.join("bin").join("node")
-            };
-            (path, "--version")
-        },
-        "go" => {
-            let path = if os_name_raw == "windows" {
-                actual_sdk_root_final.join("bin").join("go.exe")
-            } else {
-                actual_sdk_root_final.join("bin").join("go")
-            };
-            (path, "version") // Go uses "go version" not "go --version"
-        },
-        _ => { // Java vendors
-            let path = if os_name_raw == "windows" {
-                actual_sdk_root_final.join("bin").join("java.exe")
-            } else {
-                actual_sdk_root_final.join("bin").join("java")
-            };
-            (path, "-version")
+/// Runs one installation on the calling thread and records the outcome in the vendor's
+/// `LanguageState` (status, toast, last install). The tab must already be in the installing
+/// state (see LanguageState::begin_install()).
+fn run_install_job(
+    vendor: &str,
+    config: &LanguageConfig,
+    output_log: Arc<Mutex<InstallLog>>,
+    ctx: egui::Context,
+    cancel_requested: Arc<AtomicBool>,
+    install_lock: Arc<Mutex<()>>,
+    settings: &InstallSettings,
+) {
+    let app_state_id = egui::Id::new("JdkInstallerAppState");
+    // A previous worker for this vendor may still be finishing; never let two write the same directory,
+    // so this request queues behind it.
+    let install_guard = match install_lock.try_lock() {
+        Ok(guard) => guard,
+        Err(std::sync::TryLockError::Poisoned(poisoned)) => poisoned.into_inner(), // A previous worker panicked
+        Err(std::sync::TryLockError::WouldBlock) => {
+            let mut current_log = output_log.lock().expect("Failed to acquire log mutex for concurrent install");
+            current_log.push(LogLevel::Info, LogCategory::General, &format!("Another {} installation is still finishing; waiting for it.\n", vendor));
+            drop(current_log);
+            ctx.request_repaint();
+            install_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
         }
     };
-
-    let output = Command::new(&verification_command_path)
-        .arg(version_arg)
-        .output()
-        .map_err(|e| format!("Failed to execute {} verification command: {}", vendor, e))?;
-    
-    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for verification output");
-    current_log.push_str(&format!("{}", String::from_utf8_lossy(&output.stderr)));
-    current_log.push_str(&format!("{}", String::from_utf8_lossy(&output.stdout))); // Python/Rust/Node.js/Go outputs to stdout
-    drop(current_log);
-
-    if output.status.success() {
-        let installed_version_str = if vendor == "python" {
-            String::from_utf8_lossy(&output.stdout).trim().replace("Python ", "").to_string()
-        } else if vendor == "rust" {
-            String::from_utf8_lossy(&output.stdout).lines().next()
-                .unwrap_or("unknown rustc version").replace("rustc ", "").split(' ').next().unwrap_or("unknown").to_string()
-        }
-        else if vendor == "c_cpp" {
-            String::from_utf8_lossy(&output.stdout).lines().next()
-                .unwrap_or("unknown gcc version").split(' ').nth(2).unwrap_or("unknown").to_string()
-        }
-        else if vendor == "nodejs" {
-            String::from_utf8_lossy(&output.stdout).trim().replace("v", "").to_string()
-        }
-        else if vendor == "go" {
-            String::from_utf8_lossy(&output.stdout).trim().replace("go version go", "").split_whitespace().next().unwrap_or("unknown").to_string()
-        }
-        else {
-            // Parse Java version from stderr (e.g., "openjdk version "21.0.2"")
-            let stderr_str = String::from_utf8_lossy(&output.stderr);
-            stderr_str.lines().find(|line| line.contains("version"))
-                .map(|line| line.replace("openjdk version \"", "").replace("java version \"", "").trim_end_matches('"').to_string())
-                .unwrap_or_else(|| "unknown".to_string())
+    let progress = TabProgress { ctx: ctx.clone(), app_state_id, vendor: vendor.to_string(), log: output_log.clone() };
+    let result = run_installation_logic(config, &progress, cancel_requested, settings);
+    // Released before the tab stops showing "installing", so an immediate re-click isn't made to wait.
+    drop(install_guard);
+
+    if let Some(app_state_arc) = ctx.data(|d| d.get_temp::<Arc<Mutex<JdkInstallerApp>>>(app_state_id)) {
+        let mut app_state = app_state_arc.lock().expect("Failed to acquire app state mutex in spawned thread");
+        let lang = app_state.settings.language;
+        let toast = match &result {
+            Ok(_) => Toast::new(tr_format(lang, "{} {} installed successfully", &[&vendor, &if config.install_latest { tr(lang, "latest") } else { config.version.as_str() }]), false),
+            Err(e) => Toast::new(tr_format(lang, "{} installation failed: {}", &[&vendor, e]), true),
         };
-
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for successful verification");
-        current_log.push_str(&format!("{} version {} installed.\n", vendor, installed_version_str));
-        drop(current_log);
-        
-        // Check specific version compatibility for Python (and potentially others in the future)
-        if vendor == "python" {
-            // Use the version from the GUI input for compatibility check, as that's what the user *requested*
-            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Python compatibility check");
-            current_log.push_str(&format!("Checking Python version compatibility: Installed '{}' vs Required '{}'.\n", installed_version_str, version));
-            drop(current_log);
-            if !is_version_compatible(&installed_version_str, version) {
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Python version mismatch");
-                current_log.push_str(&format!("Installed Python version {} does not match required version {}.\n", installed_version_str, version));
-                drop(current_log);
-                update_app_state(&ctx, app_state_id, vendor, Some(format!("Python version mismatch: Expected {}, got {}.", version, installed_version_str)), None, None);
-                return Err(format!("Python version mismatch: Expected {}, got {}.", version, installed_version_str));
-            } else {
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Python version match");
-                current_log.push_str(&format!("Installed Python version {} matches required version {}.\n", installed_version_str, version));
-                drop(current_log);
+        app_state.toasts.push(toast);
+        if let Some(lang_state) = app_state.language_states.get_mut(vendor) {
+            lang_state.is_installing = false;
+            // Also push error to log if there was one.
+            if let Err(ref e) = result {
+                let mut log = lang_state.output_log.lock().expect("Failed to acquire log mutex to append error");
+                log.push(LogLevel::Error, LogCategory::General, &format!("{}\n", e));
             }
-
-            // --- START: PIP BOOTSTRAP AND LIBRARY INSTALLATION ---
-            let python_exe_path = if os_name_raw == "windows" {
-                actual_sdk_root_final.join("python.exe")
-            } else {
-                actual_sdk_root_final.join("bin").join("python3")
-            };
-
-            // Determine pip executable path based on OS
-            let pip_exe_path = if os_name_raw == "windows" {
-                actual_sdk_root_final.join("Scripts").join("pip.exe")
+            if let Ok(installed) = &result {
+                lang_state.last_install = Some(installed.clone());
+                lang_state.installed_scan = None; // Rescan to pick up the new version
+            }
+            lang_state.install_result = Some(result.map(|_| ()));
+            if lang_state.install_result.as_ref().expect("Install result should be Some here.").is_ok() {
+                lang_state.current_status = tr(lang, "Installation complete!").to_string();
             } else {
-                python_exe_path.clone() // Used with -m pip
-            };
-
-            // Step 1: Bootstrap pip if it's missing (common for embedded zips).
-            if os_name_raw == "windows" {
-                update_app_state(&ctx, app_state_id, vendor, Some("Downloading pip installer...".to_string()), None, None);
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for get-pip.py download start");
-                current_log.push_str("Downloading get-pip.py...\n");
-                drop(current_log);
-                let get_pip_url = "https://bootstrap.pypa.io/get-pip.py";
-                let mut get_pip_response = client.get(get_pip_url)
-                    .send().map_err(|e| format!("Failed to download get-pip.py: {}", e))?;
-                
-                let get_pip_path = actual_sdk_root_final.join("get-pip.py");
-                let mut get_pip_file = File::create(&get_pip_path)
-                    .map_err(|e| format!("Failed to create get-pip.py file: {}", e))?;
-                io::copy(&mut get_pip_response, &mut get_pip_file)
-                    .map_err(|e| format!("Failed to save get-pip.py: {}", e))?;
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for get-pip.py download complete");
-                current_log.push_str("get-pip.py download complete.\n");
-                drop(current_log);
-
-                update_app_state(&ctx, app_state_id, vendor, Some("Installing pip...".to_string()), None, None);
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for pip install start");
-                current_log.push_str("Running get-pip.py to install pip...\n");
-                drop(current_log);
-                let pip_install_output = Command::new(&python_exe_path)
-                    .arg(&get_pip_path)
-                    .output()
-                    .map_err(|e| format!("Failed to execute get-pip.py: {}", e))?;
-                
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for pip install output");
-                current_log.push_str(&format!("{}", String::from_utf8_lossy(&pip_install_output.stdout)));
-                current_log.push_str(&format!("{}", String::from_utf8_lossy(&pip_install_output.stderr)));
-                drop(current_log);
-
-                if pip_install_output.status.success() {
-                    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for pip install success");
-                    current_log.push_str("pip installed successfully.\n");
-                    drop(current_log);
-                } else {
-                    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for pip install failure");
-                    current_log.push_str("Failed to install pip using get-pip.py.\n");
-                    drop(current_log);
-                    return Err("pip installation failed. Cannot proceed with library installation.".to_string());
-                }
-
-                // Clean up get-pip.py
-                fs::remove_file(&get_pip_path)
-                    .map_err(|e| format!("Failed to remove get-pip.py: {}", e))?;
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for get-pip.py cleanup");
-                current_log.push_str("Cleaned up get-pip.py.\n");
-                drop(current_log);
-
-            } else { // Attempt ensurepip for non-Windows
-                update_app_state(&ctx, app_state_id, vendor, Some("Checking pip availability...".to_string()), None, None);
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for ensurepip start");
-                current_log.push_str("Checking pip availability...\n");
-                drop(current_log);
-                let ensurepip_output = Command::new(&python_exe_path)
-                    .arg("-m")
-                    .arg("ensurepip")
-                    .arg("--default-pip")
-                    .output()
-                    .map_err(|e| format!("Failed to bootstrap pip: {}", e))?;
-
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for ensurepip output");
-                current_log.push_str(&format!("{}", String::from_utf8_lossy(&ensurepip_output.stdout)));
-                current_log.push_str(&format!("{}", String::from_utf8_lossy(&ensurepip_output.stderr)));
-                drop(current_log);
-
-                if ensurepip_output.status.success() {
-                    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for ensurepip success");
-                    current_log.push_str("pip is now available.\n");
-                    drop(current_log);
-                } else {
-                    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for ensurepip failure");
-                    current_log.push_str("Failed to ensure pip is available. Library installation might fail.\n");
-                    drop(current_log);
-                    // Do not return Err here, allow library installation to proceed and report its own errors.
-                }
+                lang_state.current_status = tr(lang, "Installation failed.").to_string();
             }
+        }
+    }
+    ctx.request_repaint();
+}
 
+/// How long a completion toast stays on screen, including its fade-out.
+const TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(5);
 
-            // Step 2: Install Python libraries
-            let libraries: Vec<&str> = python_libraries.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()).collect();
-            if !libraries.is_empty() {
-                update_app_state(&ctx, app_state_id, vendor, Some("Installing Python libraries...".to_string()), None, None);
-                let mut current_log = log_output.lock().expect("Failed to acquire log mutex for Python library install start");
-                current_log.push_str("Installing specified Python libraries...\n");
-                drop(current_log);
-
-                for lib_spec in libraries {
-                    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for library install attempt");
-                    current_log.push_str(&format!("Attempting to install: {}\n", lib_spec));
-                    drop(current_log);
-                    let pip_install_output = if os_name_raw == "windows" {
-                        // For Windows, call pip.exe directly.
-                        Command::new(&pip_exe_path)
-                            .arg("install")
-                            .arg(lib_spec)
-                            .output()
-                            .map_err(|e| format!("Failed to execute pip install for {}: {}", lib_spec, e))?
-                    } else {
-                        // For non-Windows, use python -m pip
-                        Command::new(&python_exe_path)
-                            .arg("-m")
-                            .arg("pip")
-                            .arg("install")
-                            .arg(lib_spec)
-                            .output()
-                            .map_err(|e| format!("Failed to execute pip install for {}: {}", lib_spec, e))?
-                    };
-                    
-                    let mut current_log = log_output.lock().expect("Failed to acquire log mutex for pip install output");
-                    current_log.push_str(&format!("{}", String::from_utf8_lossy(&pip_install_output.stdout)));
-                    current_log.push_str(&format!("{}", String::from_utf8_lossy(&pip_install_output.stderr)));
-                    drop(current_log);
-
-                    if pip_install_output.status.success() {
-                        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for library install success");
-                        current_log.push_str(&format!("Successfully installed: {}\n", lib_spec));
-                        drop(current_log);
-                        
-                        // Verify installed library version
-                        let lib_name = lib_spec.split_once(&['=', '>', '<', '~'][..]).map_or(lib_spec, |(name, _)| name);
-                        let pip_show_output = if os_name_raw == "windows" {
-                            // For Windows, call pip.exe directly.
-                            Command::new(&pip_exe_path)
-                                .arg("show")
-                                .arg(lib_name)
-                                .output()
-                                .map_err(|e| format!("Failed to execute pip show for {}: {}", lib_name, e))?
-                        } else {
-                            // For non-Windows, use python -m pip
-                            Command::new(&python_exe_path)
-                                .arg("-m")
-                                .arg("pip")
-                                .arg("show")
-                                .arg(lib_name)
-                                .output()
-                                .map_err(|e| format!("Failed to execute pip show for {}: {}", lib_name, e))?
-                        };
-                        
-                        let pip_show_str = String::from_utf8_lossy(&pip_show_output.stdout);
-                        let installed_lib_version = pip_show_str.lines()
-                            .find(|line| line.starts_with("Version:"))
-                            .and_then(|line| line.split(':').nth(1))
-                            .map_or("unknown", |s| s.trim());
-
-                        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for library compatibility check");
-                        current_log.push_str(&format!("Checking library compatibility for {}: Installed '{}' vs Required '{}'.\n", lib_name, installed_lib_version, lib_spec));
-                        drop(current_log);
-                        if !is_version_compatible(installed_lib_version, lib_spec) {
-                            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for library version mismatch");
-                            current_log.push_str(&format!("Installed version of {} ({}) does not meet requirement {}.\n", lib_name, installed_lib_version, lib_spec));
-                            drop(current_log);
-                            update_app_state(&ctx, app_state_id, vendor, Some(format!("Library compatibility issue for {}: Expected {}, got {}.", lib_name, lib_spec, installed_lib_version)), None, None);
-                            return Err(format!("Library compatibility issue for {}: Expected {}, got {}.", lib_name, lib_spec, installed_lib_version));
-                        } else {
-                            let mut current_log = log_output.lock().expect("Failed to acquire log mutex for library version match");
-                            current_log.push_str(&format!("{} version verified: {} (meets requirement {}).\n", lib_name, installed_lib_version, lib_spec));
-                            drop(current_log);
-                        }
+/// A short-lived notification shown in the bottom-right corner when an installation finishes.
+struct Toast {
+    message: String,
+    is_error: bool,
+    created_at: std::time::Instant,
+}
 
-                    } else {
-                        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for library install failure");
-                        current_log.push_str(&format!("Failed to install: {}\n", lib_spec));
-                        drop(current_log);
-                        update_app_state(&ctx, app_state_id, vendor, Some(format!("Python library installation failed: {}.", lib_spec)), None, None);
-                        return Err(format!("Python library installation failed: {}.", lib_spec));
-                    }
-                }
-            }
-            // --- END: PIP BOOTSTRAP AND LIBRARY INSTALLATION ---
-        }
-        update_app_state(&ctx, app_state_id, vendor, Some(format!("{} installation complete!", vendor)), Some(1.0), Some(1.0));
-    } else {
-        let mut current_log = log_output.lock().expect("Failed to acquire log mutex for verification failure");
-        current_log.push_str(&format!("{} verification failed.", vendor));
-        drop(current_log);
-        update_app_state(&ctx, app_state_id, vendor, Some(format!("{} verification failed.", vendor)), None, None);
-        return Err(format!("{} verification failed.", vendor));
+impl Toast {
+    fn new(message: String, is_error: bool) -> Self {
+        Toast { message, is_error, created_at: std::time::Instant::now() }
     }
-    Ok(())
 }
 
-/// Represents the configuration for a specific language installation.
-struct LanguageConfig {
+/// Version list filled in by the fetch thread; `None` while loading.
+type SharedVersionList = Arc<Mutex<Option<Result<Vec<AvailableVersion>, String>>>>;
+
+/// State of the "Browse versions" window: the list is fetched on a background thread.
+struct VersionBrowser {
     vendor: String,
-    version: String,
-    install_latest: bool,
-    python_libraries_input: String, // Specific to Python.
+    versions: SharedVersionList,
+    selected: Option<usize>,
 }
 
-impl Default for LanguageConfig {
-    fn default() -> Self {
-        LanguageConfig {
-            vendor: "azul".to_owned(), // Default to Java Azul.
-            version: "21".to_owned(),
-            install_latest: false,
-            python_libraries_input: "".to_owned(),
-        }
+impl VersionBrowser {
+    fn open(vendor: &str, ctx: &egui::Context, proxy: String) -> Self {
+        let versions = Arc::new(Mutex::new(None));
+        let versions_clone = versions.clone();
+        let vendor_clone = vendor.to_owned();
+        let ctx_clone = ctx.clone();
+        std::thread::spawn(move || {
+            let result = fetch_available_versions(&vendor_clone, &proxy);
+            *versions_clone.lock().expect("Failed to acquire version list mutex") = Some(result);
+            ctx_clone.request_repaint();
+        });
+        VersionBrowser { vendor: vendor.to_owned(), versions, selected: None }
     }
 }
 
-/// Represents the runtime state of a specific language installation.
-struct LanguageState {
-    output_log: Arc<Mutex<String>>, // Shared state for logging
-    is_installing: bool,
-    install_result: Option<Result<(), String>>,
-    download_progress: f32, // 0.0 to 1.0
-    extract_progress: f32,  // 0.0 to 1.0
-    current_status: String,
-    cancel_requested: Arc<AtomicBool>,
+/// Result of the "Update all installed" scan, filled in by the scan thread; `None` while scanning.
+type SharedUpdateScan = Arc<Mutex<Option<UpdateScan>>>;
+
+/// State of the "Update all installed" window: the scan runs on a background thread, and once
+/// confirmed the updates run one after another on another.
+struct UpdateAllDialog {
+    scan: SharedUpdateScan,
+    completed: Option<Arc<AtomicUsize>>, // Updates finished so far; None until the batch is started
+    stop_requested: Arc<AtomicBool>, // Set by "Stop all"; the batch skips the updates not yet started
 }
 
-impl Default for LanguageState {
-    fn default() -> Self {
-        LanguageState {
-            output_log: Arc::new(Mutex::new(String::new())),
-            is_installing: false,
-            install_result: None,
-            download_progress: 0.0,
-            extract_progress: 0.0,
-            current_status: "Ready for installation".to_string(),
-            cancel_requested: Arc::new(AtomicBool::new(false)),
+/// A tab's log as exported by "Save log…" and "Copy log", headed by the tool version and the
+/// platform a bug report needs.
+fn log_export_text(vendor: &str, log: &InstallLog) -> String {
+    format!("toolchain {} log for {}\nOS: {}, ARCH: {}\n\n{}", env!("CARGO_PKG_VERSION"), vendor, env::consts::OS, env::consts::ARCH, log.full_text())
+}
+
+/// "Save log…" and "Copy log" buttons for the log of `vendor`'s tab.
+fn log_export_buttons(ui: &mut egui::Ui, lang: Language, vendor: &str, log: &Mutex<InstallLog>, toasts: &mut Vec<Toast>) {
+    ui.horizontal(|ui| {
+        if ui.button(tr(lang, "Save log…")).on_hover_text(tr(lang, "Saves the full log of the last run to a file, e.g. to attach to a bug report")).clicked() {
+            let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+            let target = rfd::FileDialog::new()
+                .set_file_name(format!("toolchain-{}-{}.log", vendor, timestamp))
+                .add_filter("Log", &["log", "txt"])
+                .save_file();
+            if let Some(path) = target {
+                let text = log_export_text(vendor, &log.lock().expect("Failed to acquire log mutex to save log"));
+                toasts.push(match fs::write(&path, text) {
+                    Ok(()) => Toast::new(tr_format(lang, "Log saved to {}", &[&path.display()]), false),
+                    Err(e) => Toast::new(tr_format(lang, "Could not save the log: {}", &[&e]), true),
+                });
+            }
         }
-    }
+        if ui.button(tr(lang, "Copy log")).on_hover_text(tr(lang, "Copies the full log of the last run to the clipboard")).clicked() {
+            let text = log_export_text(vendor, &log.lock().expect("Failed to acquire log mutex to copy log"));
+            ui.output_mut(|o| o.copied_text = text);
+            toasts.push(Toast::new(tr(lang, "Log copied to the clipboard").to_string(), false));
+        }
+    });
+}
+
+/// State of the "Repair PATH" maintenance dialog.
+struct PathRepairDialog {
+    stale_entries: Vec<StalePathEntry>, // Found by the scan, awaiting confirmation
+    log: String, // Scan errors and one line per removal
 }
 
+/// State of the "Uninstall" dialog for one tab.
+struct UninstallDialog {
+    vendor: String,
+    versions: Vec<(String, PathBuf)>, // Installed versions, newest first
+    pending: Option<(String, PathBuf)>, // Version awaiting confirmation
+}
 
 // Main GUI application structure
 struct JdkInstallerApp {
     language_configs: HashMap<String, LanguageConfig>,
     language_states: HashMap<String, LanguageState>,
     selected_vendor: String, // Current active "tab"
-    font_size: f32,
+    settings: PersistedSettings, // Global options; saved whenever the Settings window changes them
+    install_locations: InstallLocations, // Resolved from `settings` whenever they change
+    show_settings: bool,
+    mirror_rows: Vec<(String, String)>, // Mirror entries being edited in the Settings window
+    mirror_auth_rows: Vec<MirrorAuthRow>, // Mirror credentials being edited in the Settings window
+    bundle_path: String, // Configuration bundle file for "Export bundle"/"Import bundle"
+    mirror_config: Arc<MirrorConfig>,
+    download_limiter: Arc<DownloadLimiter>,
+    mirror_config_error: Option<String>, // Shown in the Settings window when mirrors.toml is invalid
+    toasts: Vec<Toast>, // Completion notifications pushed by worker threads
+    path_repair: Option<PathRepairDialog>, // Open "Repair PATH" dialog, if any
+    version_browser: Option<VersionBrowser>, // Open "Browse versions" window, if any
+    update_all: Option<UpdateAllDialog>, // Open "Update all installed" window, if any
+    batch_selection: BTreeSet<String>, // Tabs ticked for "Install all selected"
+    batch_pending: Vec<String>, // "Install all selected" tabs waiting for a prerequisite tab to finish
+    uninstall: Option<UninstallDialog>, // Open "Uninstall" window, if any
+    interrupted_installs: Vec<InterruptedInstall>, // Found at startup; the window shows while non-empty
+    show_compact_log: bool, // Log pane toggle in compact view
+    log_min_level: LogLevel, // Log view filters, shared by all tabs
+    log_category: Option<LogCategory>, // None shows every step
     show_cancel_confirmation: bool,
+    show_stop_all_confirmation: bool, // "Stop all" in the top panel
     show_exit_confirmation: bool, // New field for exit confirmation
 }
 
+/// Layers a high-contrast palette over the default visuals for low-vision users:
+/// pure black backgrounds, white text and borders, and thick yellow selection strokes.
+/// The progress bar fill is a saturated blue so its white percentage text stays legible.
+fn apply_high_contrast(visuals: &mut egui::Visuals) {
+    visuals.override_text_color = Some(egui::Color32::WHITE);
+    visuals.panel_fill = egui::Color32::BLACK;
+    visuals.window_fill = egui::Color32::BLACK;
+    visuals.extreme_bg_color = egui::Color32::BLACK;
+    visuals.faint_bg_color = egui::Color32::from_rgb(20, 20, 20);
+    visuals.widgets.noninteractive.bg_fill = egui::Color32::BLACK;
+    visuals.widgets.noninteractive.bg_stroke = egui::Stroke::new(1.5, egui::Color32::WHITE);
+
+    for widget in [&mut visuals.widgets.inactive, &mut visuals.widgets.hovered, &mut visuals.widgets.active] {
+        widget.bg_fill = egui::Color32::BLACK;
+        widget.weak_bg_fill = egui::Color32::BLACK;
+        widget.bg_stroke = egui::Stroke::new(2.0, egui::Color32::WHITE);
+        widget.fg_stroke = egui::Stroke::new(2.0, egui::Color32::WHITE);
+    }
+    visuals.widgets.hovered.bg_stroke = egui::Stroke::new(3.0, egui::Color32::YELLOW);
+    visuals.widgets.active.bg_stroke = egui::Stroke::new(3.0, egui::Color32::YELLOW);
+
+    visuals.selection.bg_fill = egui::Color32::from_rgb(0, 60, 200); // Progress bar fill and selected items
+    visuals.selection.stroke = egui::Stroke::new(3.0, egui::Color32::YELLOW);
+    visuals.hyperlink_color = egui::Color32::YELLOW;
+    visuals.window_stroke = egui::Stroke::new(2.0, egui::Color32::WHITE);
+}
+
 impl eframe::App for JdkInstallerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Apply font size
         let mut style = (*ctx.style()).clone();
         for (_text_style, font_id) in style.text_styles.iter_mut() {
-            font_id.size = self.font_size;
+            font_id.size = self.settings.font_size;
         }
         
         // --- START: Aesthetic improvements ---
@@ -1320,56 +1203,137 @@ impl eframe::App for JdkInstallerApp {
             color: egui::Color32::from_black_alpha(150),
         };
 
+        if self.settings.high_contrast {
+            apply_high_contrast(&mut visuals);
+        }
+
         style.visuals = visuals;
         // --- END: Aesthetic improvements ---
 
-        ctx.set_style(style); 
+        ctx.set_style(style);
+        ctx.set_zoom_factor(self.settings.ui_scale);
+        let lang = self.settings.language;
+        let previous_settings = self.settings.clone();
 
         // Top panel for main application title
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.add_space(5.0);
             egui::menu::bar(ui, |ui| {
                 ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
-                    ui.heading("Multi-Language Installer"); // Updated title
+                    ui.heading(tr(lang, APP_TITLE));
                 });
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui.button("Exit").clicked() {
+                    if ui.button(tr(lang, "Exit")).clicked() {
                         self.show_exit_confirmation = true;
                     }
+                    let any_installing = self.language_states.values().any(|state| state.is_installing);
+                    if ui.add_enabled(any_installing, egui::Button::new(tr(lang, "Stop all")))
+                        .on_hover_text(tr(lang, "Cancels every running installation, including a running \"Update all installed\""))
+                        .clicked() {
+                        self.show_stop_all_confirmation = true;
+                    }
                 });
             });
             ui.add_space(5.0);
         });
 
+        // Start "Install all selected" dependents whose prerequisites are no longer installing
+        if !self.batch_pending.is_empty() {
+            let (waiting, ready): (Vec<String>, Vec<String>) = std::mem::take(&mut self.batch_pending).into_iter()
+                .partition(|vendor| self.language_states.iter().any(|(other, state)| state.is_installing && depends_on(vendor, other)));
+            self.batch_pending = waiting;
+            for vendor in ready {
+                self.start_install(&vendor, ctx);
+            }
+        }
+
         // Side panel for language selection (vertical tabs)
         egui::SidePanel::left("side_panel").resizable(true).show(ctx, |ui| {
             ui.vertical_centered_justified(|ui| {
                 ui.add_space(10.0);
-                ui.heading("Installation Options");
+                ui.heading(tr(lang, "Installation Options"));
                 ui.add_space(10.0);
             });
 
             ui.separator();
             ui.add_space(10.0);
 
-            // Language selection buttons
+            // Language selection buttons, annotated with each tab's background install state
             ui.vertical(|ui| {
-                ui.selectable_value(&mut self.selected_vendor, "azul".to_owned(), "Java (Azul Zulu)");
-                ui.selectable_value(&mut self.selected_vendor, "temurin".to_owned(), "Java (Temurin)");
-                ui.selectable_value(&mut self.selected_vendor, "openjdk".to_owned(), "Java (OpenJDK)");
-                ui.selectable_value(&mut self.selected_vendor, "python".to_owned(), "Python");
-                ui.selectable_value(&mut self.selected_vendor, "c_cpp".to_owned(), "C/C++ (MinGW-w64)");
-                ui.selectable_value(&mut self.selected_vendor, "rust".to_owned(), "Rust");
-                ui.selectable_value(&mut self.selected_vendor, "nodejs".to_owned(), "Node.js (LTS)");
-                ui.selectable_value(&mut self.selected_vendor, "go".to_owned(), "Go");
+                for (vendor_id, label) in VENDOR_TABS {
+                    let tab_label = match self.language_states.get(vendor_id) {
+                        Some(state) if state.is_installing => {
+                            let progress = if state.extract_progress > 0.0 { state.extract_progress } else { state.download_progress };
+                            format!("{} ({:.0}%)", label, progress * 100.0)
+                        }
+                        Some(LanguageState { install_result: Some(Ok(())), .. }) => format!("{} ✔", label),
+                        Some(LanguageState { install_result: Some(Err(_)), .. }) => format!("{} ✖", label),
+                        _ => label.to_owned(),
+                    };
+                    ui.horizontal(|ui| {
+                        let mut selected = self.batch_selection.contains(vendor_id);
+                        if ui.checkbox(&mut selected, "").on_hover_text(tr(lang, "Include in \"Install all selected\"")).changed() {
+                            if selected {
+                                self.batch_selection.insert(vendor_id.to_owned());
+                            } else {
+                                self.batch_selection.remove(vendor_id);
+                            }
+                        }
+                        if ui.selectable_value(&mut self.selected_vendor, vendor_id.to_owned(), tab_label).clicked() {
+                            self.settings.selected_tab = vendor_id.to_owned();
+                            // Rescanned when the tab is next drawn.
+                            if let Some(state) = self.language_states.get_mut(vendor_id) {
+                                state.installed_scan = None;
+                            }
+                        }
+                    });
+                }
             });
 
+            ui.add_space(10.0);
+            let install_all_clicked = ui.add_enabled(!self.batch_selection.is_empty(), egui::Button::new(tr_format(lang, "Install all selected ({})", &[&self.batch_selection.len()])))
+                .on_hover_text(tr(lang, "Installs every ticked tab with its own settings at the same time, except that Elixir and the JVM tools wait for a ticked Erlang or JDK; downloads share the parallel download limit"))
+                .clicked();
+            if install_all_clicked {
+                // Dependents wait in `batch_pending` until the prerequisite they were ticked with has finished.
+                let batch: Vec<String> = self.batch_selection.iter().cloned().collect();
+                for vendor in &batch {
+                    if batch.iter().any(|other| depends_on(vendor, other)) {
+                        self.batch_pending.push(vendor.clone());
+                    } else {
+                        self.start_install(vendor, ctx);
+                    }
+                }
+            }
+
             ui.add_space(20.0);
-            ui.add(egui::Slider::new(&mut self.font_size, 10.0..=24.0).text("Font Size"));
+            if ui.button(tr(lang, "Settings")).on_hover_text(tr(lang, "Install root, network, appearance and download options")).clicked() {
+                self.show_settings = true;
+            }
+            if ui.button(tr(lang, "Update all installed")).on_hover_text(tr(lang, "Check every installed toolchain for a newer release and update the outdated ones")).clicked() && self.update_all.is_none() {
+                let scan = Arc::new(Mutex::new(None));
+                let scan_clone = scan.clone();
+                let proxy = self.settings.proxy.clone();
+                let locations = self.install_locations.clone();
+                let ctx_clone = ctx.clone();
+                std::thread::spawn(move || {
+                    let result = scan_for_updates(&locations, &proxy);
+                    *scan_clone.lock().expect("Failed to acquire update scan mutex") = Some(result);
+                    ctx_clone.request_repaint();
+                });
+                self.update_all = Some(UpdateAllDialog { scan, completed: None, stop_requested: Arc::new(AtomicBool::new(false)) });
+            }
+            if ui.button(tr(lang, "Repair PATH")).on_hover_text(tr(lang, "Find persisted PATH entries for managed toolchains that are broken or duplicated")).clicked() {
+                self.path_repair = Some(match scan_stale_path_entries(&self.install_locations.root) {
+                    Ok(stale_entries) => PathRepairDialog { stale_entries, log: String::new() },
+                    Err(e) => PathRepairDialog { stale_entries: Vec::new(), log: format!("ERROR: {}\n", e) },
+                });
+            }
             ui.add_space(10.0);
         });
 
         // Central panel for selected language's configuration, status, and output log
+        let mut install_clicked = false;
         egui::CentralPanel::default().show(ctx, |ui| {
             let current_config = self.language_configs.get_mut(&self.selected_vendor).unwrap();
             let current_state = self.language_states.get_mut(&self.selected_vendor).unwrap();
@@ -1381,285 +1345,1242 @@ impl eframe::App for JdkInstallerApp {
 
             ui.vertical(|ui| {
                 ui.add_space(10.0);
-                ui.heading(format!("{} Configuration", match self.selected_vendor.as_str() {
-                    "azul" => "Java (Azul Zulu)",
-                    "temurin" => "Java (Temurin)",
-                    "openjdk" => "Java (OpenJDK)",
-                    "python" => "Python",
-                    "c_cpp" => "C/C++",
-                    "rust" => "Rust",
-                    "nodejs" => "Node.js",
-                    "go" => "Go",
-                    _ => "Unknown Language",
-                }));
+                ui.heading(tr_format(lang, "{} Configuration", &[&vendor_label(&self.selected_vendor)]));
                 ui.add_space(10.0);
 
-                // Only Java and Python allow version input.
-                if self.selected_vendor == "python" || self.selected_vendor.starts_with("java") {
-                    ui.checkbox(&mut current_config.install_latest, "Install Latest Version");
+                let notes = vendor_notes(&self.selected_vendor, env::consts::OS);
+                if !notes.is_empty() {
+                    egui::Frame::group(ui.style()).show(ui, |ui| {
+                        ui.set_width(ui.available_width());
+                        for note in notes {
+                            ui.label(format!("ℹ {}", tr(lang, note)));
+                        }
+                    });
+                    ui.add_space(10.0);
+                }
+
+                // Java, Python, Node.js, Go, Kotlin, Ruby, Deno, Bun and Zig allow version input.
+                if takes_version_input(&self.selected_vendor) {
+                    ui.checkbox(&mut current_config.install_latest, tr(lang, "Install Latest Version"));
                     ui.add_enabled_ui(!current_config.install_latest, |ui| {
-                        ui.label("Version:");
-                        ui.text_edit_singleline(&mut current_config.version);
+                        ui.label(tr(lang, "Version:"));
+                        ui.horizontal(|ui| {
+                            ui.text_edit_singleline(&mut current_config.version);
+                            if offers_version_browser(&self.selected_vendor) && ui.button(tr(lang, "Browse versions")).on_hover_text(tr(lang, "List the releases available for this vendor")).clicked() {
+                                self.version_browser = Some(VersionBrowser::open(&self.selected_vendor, ctx, self.settings.proxy.clone()));
+                            }
+                        });
+                        if let Err(hint) = validate_version_input(&self.selected_vendor, &current_config.version) {
+                            if !current_config.install_latest {
+                                ui.colored_label(egui::Color32::RED, tr(lang, hint));
+                            }
+                        }
                     });
                 } else {
-                    // For C/C++, Rust, Node.js, Go, do not provide version selection via text input.
-                    ui.label("Version:");
-                    ui.add_enabled(false, egui::TextEdit::singleline(&mut current_config.version).hint_text("Latest supported version"));
-                    ui.label(format!("(This installer attempts to install the latest supported {} version.)", match self.selected_vendor.as_str() {
+                    // For C/C++, Rust, Erlang and Elixir, do not provide version selection via text input.
+                    ui.label(tr(lang, "Version:"));
+                    ui.add_enabled(false, egui::TextEdit::singleline(&mut current_config.version).hint_text(tr(lang, "Latest supported version")));
+                    ui.label(tr_format(lang, "(This installer attempts to install the latest supported {} version.)", &[&match self.selected_vendor.as_str() {
                         "c_cpp" => "MinGW-w64",
                         "rust" => "Rust (stable)",
-                        "nodejs" => "Node.js (LTS)",
-                        "go" => "Go",
+                        "erlang" => "Erlang/OTP",
+                        "elixir" => "Elixir",
                         _ => "",
-                    }));
+                    }]));
                     current_config.install_latest = true; // Ensure this is always true in these cases.
                 }
 
 
+                // JavaFX bundles (greyed out for vendors that don't publish them)
+                if is_java_vendor(&self.selected_vendor) {
+                    ui.add_space(10.0);
+                    let offers_fx = offers_javafx_bundle(&self.selected_vendor);
+                    ui.add_enabled(offers_fx, egui::Checkbox::new(&mut current_config.include_javafx, tr(lang, "Include JavaFX")))
+                        .on_disabled_hover_text(tr(lang, "This vendor does not publish JDK packages bundling JavaFX."));
+                }
+
+                if self.selected_vendor != "rust" { // rustup updates PATH itself
+                    ui.add_space(10.0);
+                    ui.checkbox(&mut current_config.add_to_path, tr(lang, "Add to PATH permanently"))
+                        .on_hover_text(tr(lang, "Append the install's bin directory to your user PATH (the registry on Windows, your shell profile elsewhere), so new terminals find it"));
+                }
+
+                if let Some(license_url) = license_terms_url(&self.selected_vendor) {
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut current_config.license_accepted, tr(lang, "I accept the license terms"));
+                        ui.hyperlink_to(tr(lang, "View license"), license_url);
+                    });
+                    if !current_config.license_accepted {
+                        ui.small(tr(lang, "Downloads are refused until the license is accepted."));
+                    }
+                }
+
+                // Adoptium specific options
+                if is_adoptium_vendor(&self.selected_vendor) {
+                    ui.add_space(10.0);
+                    egui::ComboBox::from_label(tr(lang, "JVM Implementation"))
+                        .selected_text(current_config.jvm_impl.as_str())
+                        .show_ui(ui, |ui| {
+                            for jvm_impl in ADOPTIUM_JVM_IMPLS {
+                                ui.selectable_value(&mut current_config.jvm_impl, jvm_impl.to_owned(), jvm_impl);
+                            }
+                        });
+                    egui::ComboBox::from_label(tr(lang, "Release Type"))
+                        .selected_text(tr(lang, if current_config.release_type == "ea" { "Early access (ea)" } else { "General availability (ga)" }))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut current_config.release_type, "ga".to_owned(), tr(lang, "General availability (ga)"));
+                            ui.selectable_value(&mut current_config.release_type, "ea".to_owned(), tr(lang, "Early access (ea)"));
+                        });
+                }
+
+                // GraalVM specific options
+                if self.selected_vendor == "graalvm" {
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        ui.label(tr(lang, "GraalVM components:"));
+                        for (component, _, _) in GRAALVM_COMPONENTS {
+                            let mut selected = current_config.graalvm_components.iter().any(|c| c == component);
+                            if ui.checkbox(&mut selected, component).changed() {
+                                current_config.graalvm_components.retain(|c| c != component);
+                                if selected {
+                                    current_config.graalvm_components.push(component.to_owned());
+                                }
+                            }
+                        }
+                    });
+                }
+
                 // Python specific options
                 if self.selected_vendor == "python" {
                     ui.add_space(10.0);
-                    ui.label("Python Libraries (e.g., 'numpy==1.20.0, pandas>=1.3.0'):");
+                    let distribution_name = PYTHON_DISTRIBUTIONS.iter()
+                        .find(|(id, _)| *id == current_config.python_distribution)
+                        .map_or(current_config.python_distribution.as_str(), |(_, name)| name);
+                    egui::ComboBox::from_label(tr(lang, "Distribution"))
+                        .selected_text(distribution_name)
+                        .show_ui(ui, |ui| {
+                            for (id, name) in PYTHON_DISTRIBUTIONS {
+                                ui.selectable_value(&mut current_config.python_distribution, id.to_owned(), name);
+                            }
+                        });
+                    ui.label(tr(lang, "Python Libraries (e.g., 'numpy==1.20.0, pandas>=1.3.0'):"));
                     ui.text_edit_singleline(&mut current_config.python_libraries_input);
+                    if current_config.python_distribution == "miniconda" {
+                        ui.small(tr(lang, "Libraries are installed with conda install."));
+                    } else {
+                        ui.checkbox(&mut current_config.install_libraries_together, tr(lang, "Install libraries in one pip call"))
+                            .on_hover_text(tr(lang, "Faster for long lists; falls back to one library at a time if the combined install fails"));
+                    }
                 }
 
-                ui.add_space(20.0);
-
-                ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
-                    if ui.add_enabled(!current_state.is_installing, egui::Button::new("Install")).clicked() {
-                        *current_state.output_log.lock().expect("Failed to acquire log mutex to clear log") = String::new(); // Corrected line
-                        current_state.is_installing = true;
-                        current_state.install_result = None;
-                        current_state.download_progress = 0.0;
-                        current_state.extract_progress = 0.0;
-                        current_state.current_status = "Starting installation process...".to_string();
-                        current_state.cancel_requested.store(false, Ordering::SeqCst);
+                ui.add_space(10.0);
+                // Equivalent CLI invocation, so users can move from clicking to scripting.
+                let cli_command = current_config.to_cli_command();
+                ui.horizontal(|ui| {
+                    if ui.button(tr(lang, "Copy install command")).on_hover_text(tr(lang, "Copies the equivalent command-line invocation for this tab's settings")).clicked() {
+                        ui.output_mut(|o| o.copied_text = cli_command.clone());
+                    }
+                    ui.monospace(&cli_command);
+                });
+                if let Some(resolved_url) = &current_state.last_resolved_url {
+                    ui.horizontal(|ui| {
+                        if ui.button(tr(lang, "Copy URL")).on_hover_text(tr(lang, "Copies the download URL resolved by the last run")).clicked() {
+                            ui.output_mut(|o| o.copied_text = resolved_url.clone());
+                        }
+                        ui.monospace(resolved_url);
+                    });
+                }
 
+                if self.selected_vendor != "rust" { // rustup manages its own toolchains
+                    ui.add_space(10.0);
+                    let scan = current_state.installed_scan.get_or_insert_with(|| start_installed_scan(&self.install_locations, &self.selected_vendor, ctx)).clone();
+                    ui.horizontal(|ui| {
+                        ui.label(tr(lang, "Installed versions:"));
+                        if ui.button(tr(lang, "Refresh")).on_hover_text(tr(lang, "Scans the install directory again")).clicked() {
+                            current_state.installed_scan = Some(start_installed_scan(&self.install_locations, &self.selected_vendor, ctx));
+                        }
+                    });
+                    let mut activate = None;
+                    let scan_guard = scan.lock().expect("Failed to acquire installed scan mutex");
+                    match scan_guard.as_ref() {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.add(egui::Spinner::new());
+                                ui.label(tr(lang, "Scanning..."));
+                            });
+                        }
+                        Some(versions) if versions.is_empty() => {
+                            ui.label(tr(lang, "None"));
+                        }
+                        Some(versions) => {
+                            for row in versions {
+                                ui.horizontal(|ui| {
+                                    if row.active {
+                                        ui.label(tr(lang, "Active"));
+                                    } else {
+                                        let set_active_clicked = ui.add_enabled(!current_state.is_installing, egui::Button::new(tr(lang, "Set as active")))
+                                            .on_hover_text(tr(lang, "Points the current link at this version and puts the link on PATH"))
+                                            .clicked();
+                                        if set_active_clicked {
+                                            activate = Some(row.path.clone());
+                                        }
+                                    }
+                                    let reported = row.reported.as_deref().unwrap_or(tr(lang, "unverified"));
+                                    ui.monospace(format!("{} ({}): {}", row.version, reported, row.path.display()));
+                                });
+                            }
+                        }
+                    }
+                    drop(scan_guard);
+                    if let Some(sdk_root) = activate {
                         let vendor_clone = self.selected_vendor.clone();
-                        let version_clone = current_config.version.clone();
-                        let install_latest_clone = current_config.install_latest;
-                        let python_libraries_clone = current_config.python_libraries_input.clone();
+                        let locations = self.install_locations.clone();
                         let output_log_clone = current_state.output_log.clone();
                         let ctx_clone = ctx.clone();
-                        let app_state_id_clone = egui::Id::new("JdkInstallerAppState"); // Still use one global ID for app state
-                        let cancel_requested_clone = current_state.cancel_requested.clone();
+                        std::thread::spawn(move || {
+                            let (level, message) = match set_active_version(&locations, &vendor_clone, &sdk_root) {
+                                Ok(steps) => (LogLevel::Info, steps.iter().map(|step| format!("{}\n", step)).collect::<String>()),
+                                Err(e) => (LogLevel::Error, format!("Could not set the active version: {}\n", e)),
+                            };
+                            output_log_clone.lock().expect("Failed to acquire log mutex for version switch").push(level, LogCategory::General, &message);
+                            *scan.lock().expect("Failed to acquire installed scan mutex") = Some(scan_installed_versions(&locations, &vendor_clone));
+                            ctx_clone.request_repaint();
+                        });
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    if let Some(installed) = current_state.last_install.clone() {
+                        let export_clicked = ui.add_enabled(!current_state.is_installing, egui::Button::new(tr(lang, "Export SBOM")))
+                            .on_hover_text(tr(lang, "Writes a CycloneDX bill of materials for the last install next to its directory"))
+                            .clicked();
+                        if export_clicked {
+                            let output_log_clone = current_state.output_log.clone();
+                            let ctx_clone = ctx.clone();
+                            std::thread::spawn(move || {
+                                let (level, message) = match write_sbom(&installed) {
+                                    Ok(sbom_path) => (LogLevel::Info, format!("SBOM written to {}\n", sbom_path.display())),
+                                    Err(e) => (LogLevel::Error, format!("SBOM export failed: {}\n", e)),
+                                };
+                                output_log_clone.lock().expect("Failed to acquire log mutex for SBOM export").push(level, LogCategory::General, &message);
+                                ctx_clone.request_repaint();
+                            });
+                        }
+                    }
+                    if self.selected_vendor != "rust" {
+                        let verify_clicked = ui.add_enabled(!current_state.is_installing, egui::Button::new(tr(lang, "Verify integrity")))
+                            .on_hover_text(tr(lang, "Compares the installed files with the hashes recorded at install time"))
+                            .clicked();
+                        if verify_clicked {
+                            let vendor_clone = self.selected_vendor.clone();
+                            let locations = self.install_locations.clone();
+                            let last_install_path = current_state.last_install.as_ref().map(|installed| installed.path.clone());
+                            let output_log_clone = current_state.output_log.clone();
+                            let ctx_clone = ctx.clone();
+                            std::thread::spawn(move || {
+                                let (level, message) = run_integrity_check(&locations, &vendor_clone, last_install_path);
+                                output_log_clone.lock().expect("Failed to acquire log mutex for integrity check").push(level, LogCategory::Verify, &message);
+                                ctx_clone.request_repaint();
+                            });
+                        }
+                    }
+                    if self.selected_vendor != "rust" {
+                        let uninstall_clicked = ui.add_enabled(!current_state.is_installing, egui::Button::new(tr(lang, "Uninstall…")))
+                            .on_hover_text(tr(lang, "Removes an installed version together with the PATH and home variable entries this tool set up for it"))
+                            .clicked();
+                        if uninstall_clicked {
+                            self.uninstall = Some(UninstallDialog {
+                                vendor: self.selected_vendor.clone(),
+                                versions: installed_versions(&self.install_locations, &self.selected_vendor),
+                                pending: None,
+                            });
+                        }
+                    }
+                });
+
+                ui.add_space(20.0);
+
+                ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
+                    // "Install Latest Version" needs no version, so it bypasses the check.
+                    let version_valid = current_config.install_latest || validate_version_input(&self.selected_vendor, &current_config.version).is_ok();
+                    if ui.add_enabled(!current_state.is_installing && version_valid, egui::Button::new(tr(lang, "Install"))).clicked() && !current_state.is_installing {
+                        install_clicked = true;
+                    }
+                });
+
+                if self.settings.compact_view {
+                    // Compact view: one status line and an optional full log pane.
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if current_state.is_installing {
+                            let overall_progress = (current_state.download_progress + current_state.extract_progress) / 2.0; // Same measure as the title and tray tooltip
+                            ui.add(egui::ProgressBar::new(overall_progress).desired_width(120.0).show_percentage());
+                            if current_state.is_progress_indeterminate() {
+                                ui.add(egui::Spinner::new());
+                            }
+                            ui.small(&current_state.current_status);
+                            if ui.small_button(tr(lang, "Cancel")).clicked() {
+                                self.show_cancel_confirmation = true;
+                            }
+                        } else {
+                            match &current_state.install_result {
+                                Some(Ok(_)) => ui.small(tr(lang, "Installation Complete!")),
+                                Some(Err(e)) => ui.colored_label(egui::Color32::RED, egui::RichText::new(tr_format(lang, "Installation Failed: {}", &[e])).small()),
+                                None => ui.small(&current_state.current_status),
+                            };
+                        }
+                    });
+                    ui.checkbox(&mut self.show_compact_log, tr(lang, "Show log"));
+                    if self.show_compact_log {
+                        log_filter_controls(ui, lang, &mut self.log_min_level, &mut self.log_category);
+                        egui::ScrollArea::vertical().id_source("compact_log_scroll_area").stick_to_bottom(true).show(ui, |ui| {
+                            let log_content = current_state.output_log.lock().expect("Failed to acquire log mutex for compact log");
+                            show_log_entries(ui, &log_content, self.log_min_level, self.log_category);
+                        });
+                    }
+                } else {
+                    ui.add_space(10.0);
+                    ui.heading(tr(lang, "Current Status"));
+                    ui.add_space(5.0);
+
+                    if current_state.is_installing {
+                        ui.horizontal(|ui| {
+                            if current_state.is_progress_indeterminate() {
+                                ui.add(egui::Spinner::new());
+                            }
+                            ui.label(&current_state.current_status);
+                        });
+                        ui.add_space(5.0);
+                        ui.add(egui::ProgressBar::new(current_state.download_progress).show_percentage().text(tr(lang, "Downloading...")));
+                        ui.add_space(5.0);
+                        ui.add(egui::ProgressBar::new(current_state.extract_progress).show_percentage().text(tr(lang, "Extracting...")));
+                        if self.selected_vendor == "python" && !current_config.python_libraries_input.trim().is_empty() {
+                            ui.add_space(5.0);
+                            ui.add(egui::ProgressBar::new(current_state.post_install_progress).show_percentage().text(tr(lang, "Installing libraries...")));
+                        }
+                    
+                        ui.add_space(10.0);
+                        if ui.button(tr(lang, "Cancel Installation")).clicked() {
+                            self.show_cancel_confirmation = true;
+                        }
+
+                    } else if let Some(result) = &current_state.install_result {
+                        match result {
+                            Ok(_) => ui.label(tr(lang, "Installation Complete!")),
+                            Err(e) => ui.colored_label(egui::Color32::RED, tr_format(lang, "Installation Failed: {}", &[e])),
+                        };
+                    }
+
+                    ui.add_space(10.0);
+                    ui.separator();
+
+                    // Conditional display of Python specific details vs general log
+                    if self.selected_vendor == "python" {
+                        ui.add_space(10.0);
+                        ui.heading(tr(lang, "Python Details"));
+                        ui.add_space(5.0);
+
+                        ui.horizontal(|ui| {
+                            // Left column: Python Version
+                            ui.with_layout(egui::Layout::top_down(egui::Align::LEFT).with_main_wrap(true), |ui| {
+                                ui.set_width(ui.available_width() / 2.0 - 5.0);
+                                ui.heading(tr(lang, "Python Version"));
+                                ui.add_space(5.0);
+                                egui::ScrollArea::vertical().id_source("python_version_scroll_area").stick_to_bottom(true).show(ui, |ui| {
+                                    let log_content = current_state.output_log.lock().expect("Failed to acquire log mutex for Python version display");
+                                    show_log_entries(ui, &log_content, LogLevel::Info, Some(LogCategory::Verify));
+                                });
+                            });
+
+                            ui.separator(); // Vertical separator
+
+                            // Right column: Library Compatibility
+                            ui.with_layout(egui::Layout::top_down(egui::Align::LEFT).with_main_wrap(true), |ui| {
+                                ui.set_width(ui.available_width());
+                                ui.heading(tr(lang, "Library Compatibility"));
+                                ui.add_space(5.0);
+                                egui::ScrollArea::vertical().id_source("library_compatibility_scroll_area").stick_to_bottom(true).show(ui, |ui| {
+                                    let log_content = current_state.output_log.lock().expect("Failed to acquire log mutex for library compatibility display");
+                                    show_log_entries(ui, &log_content, LogLevel::Info, Some(LogCategory::Pip));
+                                });
+                            });
+                        });
+                        ui.add_space(10.0);
+                        ui.separator();
+                        ui.add_space(10.0);
+                        ui.heading(tr(lang, "Full Output Log (Python related only)"));
+                        ui.add_space(5.0);
+                        log_export_buttons(ui, lang, &self.selected_vendor, &current_state.output_log, &mut self.toasts);
+                        log_filter_controls(ui, lang, &mut self.log_min_level, &mut self.log_category);
+                        ui.add_space(5.0);
+                        egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+                            let log_content = current_state.output_log.lock().expect("Failed to acquire log mutex for full Python log");
+                            show_log_entries(ui, &log_content, self.log_min_level, self.log_category);
+                        });
+                    } else {
+                        // General log for other vendors
+                        ui.add_space(10.0);
+                        ui.heading(tr_format(lang, "Detailed Output Log ({})", &[&vendor_label(&self.selected_vendor)]));
+                        ui.add_space(5.0);
+                        log_export_buttons(ui, lang, &self.selected_vendor, &current_state.output_log, &mut self.toasts);
+                        log_filter_controls(ui, lang, &mut self.log_min_level, &mut self.log_category);
+                        ui.add_space(5.0);
+                        egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+                            let log_content = current_state.output_log.lock().expect("Failed to acquire log mutex for general log");
+                            show_log_entries(ui, &log_content, self.log_min_level, self.log_category);
+                        });
+                    }
+                }
+            });
+        });
+        if install_clicked {
+            let vendor = self.selected_vendor.clone();
+            self.start_install(&vendor, ctx);
+        }
+
+        // Show completion toasts, fading out during their last second
+        self.toasts.retain(|toast| toast.created_at.elapsed() < TOAST_DURATION);
+        let mut toast_offset = -10.0;
+        for (index, toast) in self.toasts.iter().enumerate() {
+            let remaining = TOAST_DURATION.saturating_sub(toast.created_at.elapsed()).as_secs_f32();
+            let opacity = remaining.min(1.0);
+            let text_color = if toast.is_error { egui::Color32::from_rgb(255, 100, 100) } else { egui::Color32::WHITE };
+            let response = egui::Area::new(egui::Id::new("completion_toast").with(index))
+                .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, toast_offset))
+                .order(egui::Order::Foreground)
+                .interactable(false)
+                .show(ctx, |ui| {
+                    ui.set_opacity(opacity);
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.colored_label(text_color, &toast.message);
+                    });
+                });
+            toast_offset -= response.response.rect.height() + 5.0;
+        }
+        if !self.toasts.is_empty() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        }
+
+        // Show the Settings window (if open). Changes are saved at the end of the frame.
+        if self.show_settings {
+            let mut keep_open = true;
+            let mut save_mirrors = false;
+            let mut bundle_action: Option<bool> = None; // Some(true) to export, Some(false) to import
+            egui::Window::new(tr(lang, "Settings"))
+                .open(&mut keep_open)
+                .collapsible(false)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    ui.heading(tr(lang, "Installation"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr(lang, "Install root:"));
+                        ui.add(egui::TextEdit::singleline(&mut self.settings.install_root).hint_text(tr(lang, "Default for this platform")));
+                    });
+                    ui.small(tr_format(lang, "Toolchains are installed under {}", &[&self.install_locations.root.display()]));
+                    ui.horizontal(|ui| {
+                        ui.label(tr(lang, "Java install base:"));
+                        ui.add(egui::TextEdit::singleline(&mut self.settings.java_install_base).hint_text("/opt/java"));
+                    });
+                    ui.small(tr(lang, "Java vendors install into <base>/<vendor>-<version> when set; leave empty to use the install root."));
+                    if cfg!(target_os = "linux") {
+                        ui.checkbox(&mut self.settings.write_java_profile, tr(lang, "Update /etc/profile.d/jdkm-java.sh after Java installs"))
+                            .on_hover_text(tr(lang, "Sets JAVA_HOME and PATH for all users' login shells; requires running as root"));
+                    }
+                    egui::ComboBox::from_label(tr(lang, "Architecture"))
+                        .selected_text(if self.settings.architecture.is_empty() { tr(lang, "Detect") } else { self.settings.architecture.as_str() })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.settings.architecture, String::new(), tr(lang, "Detect"));
+                            for arch in TARGET_ARCHITECTURES {
+                                ui.selectable_value(&mut self.settings.architecture, arch.to_owned(), arch);
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr(lang, "Detect uses the native architecture, including on Apple Silicon when running under Rosetta"));
+                    ui.checkbox(&mut self.settings.link_current_version, tr(lang, "Maintain a \"current\" link per vendor"))
+                        .on_hover_text(tr(lang, "Points <vendor>_versions/current at the last installed version, so a PATH entry using it survives version switches"));
+
+                    ui.add_space(10.0);
+                    ui.heading(tr(lang, "Network"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr(lang, "Proxy:"));
+                        ui.add(egui::TextEdit::singleline(&mut self.settings.proxy).hint_text("http://proxy.example.com:8080"));
+                    });
+                    ui.add(egui::Slider::new(&mut self.settings.download_timeout_secs, 60..=3600).text(tr(lang, "Download Timeout (s)")));
+                    ui.add(egui::Slider::new(&mut self.settings.api_retries, 0..=MAX_RETRIES).text(tr(lang, "API Retries")));
+                    ui.add(egui::Slider::new(&mut self.settings.api_retry_backoff_ms, 0..=30_000).text(tr(lang, "API Retry Backoff (ms)")));
+                    ui.add(egui::Slider::new(&mut self.settings.download_retries, 0..=MAX_RETRIES).text(tr(lang, "Download Retries")));
+                    ui.add(egui::Slider::new(&mut self.settings.download_retry_backoff_ms, 0..=120_000).text(tr(lang, "Download Retry Backoff (ms)")));
+                    ui.small(tr(lang, "The backoff doubles after each failed retry."));
+                    ui.add(egui::Slider::new(&mut self.settings.max_parallel_downloads, 1..=8).text(tr(lang, "Max Parallel Downloads")));
+                    ui.add(egui::Slider::new(&mut self.settings.max_log_kib, 256..=16_384).logarithmic(true).text(tr(lang, "Log Size Limit per Tab (KiB)")))
+                        .on_hover_text(tr(lang, "Older log lines are dropped from the window past this size; the full log is kept in the logs folder of the config directory"));
+                    ui.add(egui::Slider::new(&mut self.settings.bandwidth_limit_kib, 0..=102_400).logarithmic(true).text(tr(lang, "Bandwidth Cap per Download (KiB/s, 0 = unlimited)")));
+
+                    ui.add_space(10.0);
+                    ui.heading(tr(lang, "Mirrors"));
+                    if let Some(error) = &self.mirror_config_error {
+                        ui.colored_label(egui::Color32::YELLOW, tr_format(lang, "Mirrors disabled: {}", &[error]));
+                    }
+                    ui.small(tr(lang, "Key: a vendor ID or host name. Value: the base URL replacing the download URL's scheme and host."));
+                    let mut removed_row = None;
+                    for (index, (key, base_url)) in self.mirror_rows.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(key).hint_text("temurin or github.com").desired_width(140.0));
+                            ui.add(egui::TextEdit::singleline(base_url).hint_text("https://mirror.example.com"));
+                            if ui.small_button(tr(lang, "Remove")).clicked() {
+                                removed_row = Some(index);
+                            }
+                        });
+                    }
+                    if let Some(index) = removed_row {
+                        self.mirror_rows.remove(index);
+                    }
+                    ui.add_space(5.0);
+                    ui.label(tr(lang, "Mirror credentials"));
+                    ui.small(tr(lang, "Sent only to the exact host. Leave the user empty to send the secret as a bearer token. Secrets are kept in the OS keychain."));
+                    let mut removed_auth_row = None;
+                    for (index, row) in self.mirror_auth_rows.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut row.host).hint_text("artifacts.example.com").desired_width(140.0));
+                            ui.add(egui::TextEdit::singleline(&mut row.username).hint_text(tr(lang, "User")).desired_width(90.0));
+                            ui.add(egui::TextEdit::singleline(&mut row.secret).password(true).hint_text(tr(lang, "Password or token (unchanged)")));
+                            if ui.small_button(tr(lang, "Remove")).clicked() {
+                                removed_auth_row = Some(index);
+                            }
+                        });
+                    }
+                    if let Some(index) = removed_auth_row {
+                        self.mirror_auth_rows.remove(index);
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button(tr(lang, "Add mirror")).clicked() {
+                            self.mirror_rows.push((String::new(), String::new()));
+                        }
+                        if ui.button(tr(lang, "Add credentials")).clicked() {
+                            self.mirror_auth_rows.push(MirrorAuthRow { host: String::new(), username: String::new(), secret: String::new() });
+                        }
+                        if ui.button(tr(lang, "Save mirrors")).clicked() {
+                            save_mirrors = true;
+                        }
+                    });
+
+                    ui.add_space(10.0);
+                    ui.heading(tr(lang, "Appearance"));
+                    egui::ComboBox::from_label(tr(lang, "Language"))
+                        .selected_text(self.settings.language.native_name())
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                ui.selectable_value(&mut self.settings.language, language, language.native_name());
+                            }
+                        });
+                    ui.checkbox(&mut self.settings.high_contrast, tr(lang, "High Contrast Theme"));
+                    ui.add(egui::Slider::new(&mut self.settings.font_size, 10.0..=24.0).text(tr(lang, "Font Size")));
+                    ui.add(egui::Slider::new(&mut self.settings.ui_scale, 0.75..=2.0).text(tr(lang, "UI Scale")));
+                    ui.checkbox(&mut self.settings.compact_view, tr(lang, "Compact View"))
+                        .on_hover_text(tr(lang, "Collapse the status and log panels into a single line"));
+                    if cfg!(all(feature = "tray", not(target_os = "linux"))) {
+                        ui.checkbox(&mut self.settings.tray_enabled, tr(lang, "Show tray icon"))
+                            .on_hover_text(tr(lang, "Shows install progress in the system tray, with Restore and Quit"));
+                    }
+
+                    ui.add_space(10.0);
+                    ui.heading(tr(lang, "Configuration Bundle"));
+                    ui.small(tr(lang, "Settings and mirrors in one zip file, to set up another machine the same way. Mirror secrets stay in the keychain."));
+                    ui.horizontal(|ui| {
+                        ui.label(tr(lang, "File:"));
+                        ui.text_edit_singleline(&mut self.bundle_path);
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button(tr(lang, "Export bundle")).clicked() {
+                            bundle_action = Some(true);
+                        }
+                        if ui.button(tr(lang, "Import bundle")).clicked() {
+                            bundle_action = Some(false);
+                        }
+                    });
+                });
+            self.show_settings = keep_open;
+
+            if save_mirrors {
+                let mirrors = MirrorConfig {
+                    mirrors: self.mirror_rows.iter()
+                        .filter(|(key, base_url)| !key.trim().is_empty() && !base_url.trim().is_empty())
+                        .map(|(key, base_url)| (key.trim().to_string(), base_url.trim().to_string()))
+                        .collect(),
+                    auth: self.mirror_auth_rows.iter()
+                        .filter(|row| !row.host.trim().is_empty())
+                        .map(|row| (row.host.trim().to_string(), MirrorAuth { username: row.username.trim().to_string() }))
+                        .collect(),
+                };
+                // Update the keychain first, so the file never names a host whose secret failed to save.
+                let keychain_result = self.mirror_auth_rows.iter()
+                    .filter(|row| !row.host.trim().is_empty() && !row.secret.is_empty())
+                    .try_for_each(|row| store_mirror_secret(row.host.trim(), &row.secret))
+                    .and_then(|()| self.mirror_config.auth.keys()
+                        .filter(|host| !mirrors.auth.contains_key(*host))
+                        .try_for_each(|host| delete_mirror_secret(host)));
+                match keychain_result.and_then(|()| save_mirror_config(&mirrors)) {
+                    Ok(()) => {
+                        self.mirror_config = Arc::new(mirrors);
+                        self.mirror_config_error = None;
+                        for row in &mut self.mirror_auth_rows {
+                            row.secret.clear();
+                        }
+                        self.toasts.push(Toast::new(tr(lang, "Mirrors saved").to_string(), false));
+                    }
+                    Err(e) => self.toasts.push(Toast::new(tr_format(lang, "Could not save mirrors: {}", &[&e]), true)),
+                }
+            }
+            if let Some(export) = bundle_action {
+                let bundle = PathBuf::from(self.bundle_path.trim());
+                let result = if export { export_config_bundle(&bundle) } else { import_config_bundle(&bundle) };
+                match result {
+                    Ok(file_count) if export => self.toasts.push(Toast::new(tr_format(lang, "Exported {} files to {}", &[&file_count, &bundle.display()]), false)),
+                    Ok(file_count) => {
+                        // Pick up the restored files as if the app had just started.
+                        let (settings, warning) = load_persisted_settings();
+                        self.settings = settings;
+                        if let Some(warning) = warning {
+                            self.toasts.push(Toast::new(warning, true));
+                        }
+                        match load_mirror_config() {
+                            Ok(config) => {
+                                (self.mirror_rows, self.mirror_auth_rows) = config.editable_rows();
+                                self.mirror_config = Arc::new(config);
+                                self.mirror_config_error = None;
+                            }
+                            Err(e) => self.mirror_config_error = Some(e),
+                        }
+                        self.toasts.push(Toast::new(tr_format(lang, "Imported {} files from {}", &[&file_count, &bundle.display()]), false));
+                    }
+                    Err(e) => self.toasts.push(Toast::new(tr_format(lang, "Bundle failed: {}", &[&e]), true)),
+                }
+            }
+        }
 
-                        std::thread::spawn(move || {
-                            let result = run_installation_logic(
-                                &vendor_clone,
-                                &version_clone,
-                                install_latest_clone,
-                                &python_libraries_clone,
-                                output_log_clone.clone(), // Pass Arc<Mutex<String>> directly
-                                ctx_clone.clone(),
-                                app_state_id_clone,
-                                cancel_requested_clone,
-                            );
-                            
-                            if let Some(app_state_arc) = ctx_clone.data(|d| d.get_temp::<Arc<Mutex<JdkInstallerApp>>>(app_state_id_clone)) {
-                                let mut app_state = app_state_arc.lock().expect("Failed to acquire app state mutex in spawned thread");
-                                if let Some(lang_state) = app_state.language_states.get_mut(&vendor_clone) {
-                                    lang_state.is_installing = false;
-                                    // Also push error to log if there was one.
-                                    if let Err(ref e) = result {
-                                        let mut log = lang_state.output_log.lock().expect("Failed to acquire log mutex to append error");
-                                        log.push_str(&format!("ERROR: {}\n", e));
+        // Show the "Browse versions" window (if open). Double-clicking a row fills the version field.
+        if let Some(browser) = &mut self.version_browser {
+            let mut keep_open = true;
+            let mut picked_version: Option<String> = None;
+            egui::Window::new(tr_format(lang, "Available {} versions", &[&browser.vendor]))
+                .open(&mut keep_open)
+                .collapsible(false)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    let versions = browser.versions.lock().expect("Failed to acquire version list mutex");
+                    match versions.as_ref() {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(tr(lang, "Fetching available versions..."));
+                            });
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(egui::Color32::RED, tr_format(lang, "Failed to fetch versions: {}", &[e]));
+                        }
+                        Some(Ok(list)) => {
+                            ui.label(tr(lang, "Double-click a version to use it."));
+                            ui.add_space(5.0);
+                            egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                                egui::Grid::new("available_versions_grid").striped(true).num_columns(3).show(ui, |ui| {
+                                    ui.strong(tr(lang, "Version"));
+                                    ui.strong(tr(lang, "Release date"));
+                                    ui.strong("LTS");
+                                    ui.end_row();
+                                    for (index, available) in list.iter().enumerate() {
+                                        let row = ui.selectable_label(browser.selected == Some(index), &available.version);
+                                        if row.clicked() {
+                                            browser.selected = Some(index);
+                                        }
+                                        if row.double_clicked() {
+                                            picked_version = Some(available.version.clone());
+                                        }
+                                        ui.label(available.release_date.as_deref().unwrap_or("-"));
+                                        ui.label(match available.lts {
+                                            Some(true) => tr(lang, "Yes"),
+                                            Some(false) => "",
+                                            None => "-",
+                                        });
+                                        ui.end_row();
                                     }
-                                    lang_state.install_result = Some(result);
-                                    if lang_state.install_result.as_ref().expect("Install result should be Some here.").is_ok() {
-                                        lang_state.current_status = "Installation complete!".to_string();
-                                    } else {
-                                        lang_state.current_status = "Installation failed.".to_string();
+                                });
+                            });
+                        }
+                    }
+                });
+            if let Some(version) = picked_version {
+                if let Some(config) = self.language_configs.get_mut(&browser.vendor) {
+                    config.version = version;
+                    config.install_latest = false;
+                }
+                keep_open = false;
+            }
+            if !keep_open {
+                self.version_browser = None;
+            }
+        }
+
+        // Show the "Update all installed" window (if open)
+        if let Some(dialog) = &mut self.update_all {
+            let mut close_dialog = false;
+            let mut start_batch = None;
+            let scan = dialog.scan.lock().expect("Failed to acquire update scan mutex");
+            egui::Window::new(tr(lang, "Update all installed"))
+                .collapsible(false)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    let Some(scan) = scan.as_ref() else {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::Spinner::new());
+                            ui.label(tr(lang, "Checking installed toolchains for updates..."));
+                        });
+                        return;
+                    };
+                    match &dialog.completed {
+                        None => {
+                            if scan.updates.is_empty() {
+                                ui.label(tr(lang, "All installed toolchains are up to date."));
+                            } else {
+                                ui.label(tr(lang, "The following toolchains will be updated, one after another:"));
+                                ui.add_space(5.0);
+                                for update in &scan.updates {
+                                    ui.monospace(format!("{}: {} -> {}", update.vendor, update.installed_version, update.latest_version));
+                                }
+                            }
+                            for note in &scan.notes {
+                                ui.small(note);
+                            }
+                            ui.add_space(10.0);
+                            ui.horizontal(|ui| {
+                                if !scan.updates.is_empty() && ui.button(tr_format(lang, "Update {} toolchains", &[&scan.updates.len()])).clicked() {
+                                    start_batch = Some(scan.updates.clone());
+                                }
+                                if ui.button(tr(lang, "Close")).clicked() {
+                                    close_dialog = true;
+                                }
+                            });
+                        }
+                        Some(completed) => {
+                            let total = scan.updates.len();
+                            let done = completed.load(Ordering::SeqCst).min(total);
+                            // Count the running update's own progress, so the bar moves during each download.
+                            let current_progress = scan.updates.get(done)
+                                .and_then(|update| self.language_states.get(&update.vendor))
+                                .map_or(0.0, |state| (state.download_progress + state.extract_progress) / 2.0);
+                            ui.add(egui::ProgressBar::new((done as f32 + current_progress) / total as f32).show_percentage());
+                            match scan.updates.get(done) {
+                                Some(update) => {
+                                    ui.label(tr_format(lang, "Updating {} ({} of {})...", &[&update.vendor, &(done + 1), &total]));
+                                }
+                                None => {
+                                    ui.label(tr(lang, if dialog.stop_requested.load(Ordering::SeqCst) { "Updates stopped." } else { "All updates finished." }));
+                                    if ui.button(tr(lang, "Close")).clicked() {
+                                        close_dialog = true;
                                     }
                                 }
                             }
-                            ctx_clone.request_repaint(); 
-                        });
+                        }
                     }
                 });
+            drop(scan);
+            if let Some(updates) = start_batch {
+                let completed = Arc::new(AtomicUsize::new(0));
+                dialog.completed = Some(completed.clone());
+                let mut jobs: Vec<(String, LanguageConfig)> = updates.into_iter()
+                    .filter_map(|update| {
+                        let mut config = self.language_configs.get(&update.vendor)?.clone();
+                        if is_java_vendor(&update.vendor) {
+                            // "latest" would move to the newest feature release; the feature version picks its newest update.
+                            config.version = java_feature_version(&update.installed_version).to_string();
+                            config.install_latest = false;
+                        } else {
+                            config.install_latest = true;
+                        }
+                        Some((update.vendor, config))
+                    })
+                    .collect();
+                // Jobs run one after another, so updating prerequisites first is enough.
+                let prerequisites: Vec<String> = jobs.iter().map(|(vendor, _)| vendor.clone()).collect();
+                jobs.sort_by_key(|(vendor, _)| prerequisites.iter().any(|other| depends_on(vendor, other)));
+                let install_settings = self.settings.install_settings(self.mirror_config.clone(), self.download_limiter.clone());
+                let stop_requested = dialog.stop_requested.clone();
+                let ctx_clone = ctx.clone();
+                std::thread::spawn(move || run_update_batch(jobs, completed, stop_requested, ctx_clone, install_settings));
+            }
+            if close_dialog {
+                self.update_all = None;
+            }
+        }
 
-                ui.add_space(10.0);
-                ui.heading("Current Status");
-                ui.add_space(5.0);
-
-                if current_state.is_installing {
-                    ui.label(&current_state.current_status);
-                    ui.add_space(5.0);
-                    ui.add(egui::ProgressBar::new(current_state.download_progress).show_percentage().text("Downloading..."));
+        // Offer to clean up installs interrupted by a crash or kill in a previous run
+        if !self.interrupted_installs.is_empty() {
+            let mut dismissed = false;
+            let mut resume = None;
+            let mut cleanup_errors = Vec::new();
+            egui::Window::new(tr(lang, "Interrupted Installations"))
+                .collapsible(false)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    ui.label(tr(lang, "These installations did not finish last time. Resume one to install it again on its tab, or clean them all up:"));
                     ui.add_space(5.0);
-                    ui.add(egui::ProgressBar::new(current_state.extract_progress).show_percentage().text("Extracting..."));
-                    
-                    ui.add_space(10.0);
-                    if ui.button("Cancel Installation").clicked() {
-                        self.show_cancel_confirmation = true;
+                    for (index, install) in self.interrupted_installs.iter().enumerate() {
+                        let state = if install.dir.exists() { tr(lang, "partially installed") } else { tr(lang, "not yet extracted") };
+                        ui.horizontal(|ui| {
+                            ui.monospace(format!("{}: {} ({})", install.vendor, install.dir.display(), state));
+                            let busy = self.language_states.get(&install.vendor).is_some_and(|state| state.is_installing);
+                            if ui.add_enabled(!busy, egui::Button::new(tr(lang, "Resume")))
+                                .on_hover_text(tr(lang, "Installs this version again; a partial download continues where it stopped"))
+                                .clicked() {
+                                resume = Some(index);
+                            }
+                        });
                     }
-
-                } else if let Some(result) = &current_state.install_result {
-                    match result {
-                        Ok(_) => ui.label("Installation Complete!"),
-                        Err(e) => ui.colored_label(egui::Color32::RED, format!("Installation Failed: {}", e)),
-                    };
-                }
-
-                ui.add_space(10.0);
-                ui.separator();
-
-                // Conditional display of Python specific details vs general log
-                if self.selected_vendor == "python" {
                     ui.add_space(10.0);
-                    ui.heading("Python Details");
-                    ui.add_space(5.0);
-
                     ui.horizontal(|ui| {
-                        // Left column: Python Version
-                        ui.with_layout(egui::Layout::top_down(egui::Align::LEFT).with_main_wrap(true), |ui| {
-                            ui.set_width(ui.available_width() / 2.0 - 5.0);
-                            ui.heading("Python Version");
-                            ui.add_space(5.0);
-                            egui::ScrollArea::vertical().id_source("python_version_scroll_area").stick_to_bottom(true).show(ui, |ui| {
-                                let log_content = current_state.output_log.lock().expect("Failed to acquire log mutex for Python version display");
-                                let filtered_log: String = log_content.lines()
-                                    .filter(|line| {
-                                        line.contains("Python") ||
-                                        line.contains("PYTHON_HOME") ||
-                                        line.contains("Checking version") ||
-                                        line.contains("Installed Python version") ||
-                                        line.contains("Python version mismatch") ||
-                                        line.contains("pip installer") ||
-                                        line.contains("get-pip.py") ||
-                                        line.contains("Installing pip")
-                                    })
-                                    .collect::<Vec<&str>>()
-                                    .join("\n");
-                                ui.monospace(filtered_log);
-                            });
-                        });
+                        if ui.button(tr(lang, "Clean up")).clicked() {
+                            cleanup_errors = self.interrupted_installs.iter().filter_map(|install| remove_interrupted_install(install).err()).collect();
+                            dismissed = true;
+                        }
+                        if ui.button(tr(lang, "Keep for now")).on_hover_text(tr(lang, "Installing the same version again also replaces the leftovers")).clicked() {
+                            dismissed = true;
+                        }
+                    });
+                });
+            for e in cleanup_errors {
+                self.toasts.push(Toast::new(e, true));
+            }
+            if let Some(index) = resume {
+                let install = self.interrupted_installs.remove(index);
+                self.resume_interrupted_install(&install, ctx);
+            }
+            if dismissed {
+                self.interrupted_installs.clear();
+            }
+        }
 
-                        ui.separator(); // Vertical separator
+        // Show the "Repair PATH" dialog (if open)
+        if let Some(dialog) = &mut self.path_repair {
+            let mut close_dialog = false;
+            egui::Window::new(tr(lang, "Repair PATH"))
+                .collapsible(false)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    if dialog.stale_entries.is_empty() {
+                        ui.label(tr(lang, "No broken or duplicated PATH entries for managed toolchains were found."));
+                    } else {
+                        ui.label(tr(lang, "The following persisted PATH entries point into managed toolchain directories and can be removed:"));
+                        ui.add_space(5.0);
+                        for entry in &dialog.stale_entries {
+                            let location = match entry.line_number {
+                                Some(n) => format!("{}:{}", entry.location, n + 1),
+                                None => entry.location.clone(),
+                            };
+                            ui.monospace(format!("{} ({}): {}", location, entry.reason, entry.entry.trim()));
+                        }
+                    }
+                    if !dialog.log.is_empty() {
+                        ui.add_space(5.0);
+                        ui.monospace(&dialog.log);
+                    }
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if !dialog.stale_entries.is_empty() && ui.button(tr(lang, "Clean up")).clicked() {
+                            match remove_stale_path_entries(&dialog.stale_entries) {
+                                Ok(removals) => {
+                                    for removal in removals {
+                                        dialog.log.push_str(&format!("{}\n", removal));
+                                    }
+                                    dialog.stale_entries.clear();
+                                }
+                                Err(e) => dialog.log.push_str(&format!("ERROR: {}\n", e)),
+                            }
+                        }
+                        if ui.button(tr(lang, "Close")).clicked() {
+                            close_dialog = true;
+                        }
+                    });
+                });
+            if close_dialog {
+                self.path_repair = None;
+            }
+        }
 
-                        // Right column: Library Compatibility
-                        ui.with_layout(egui::Layout::top_down(egui::Align::LEFT).with_main_wrap(true), |ui| {
-                            ui.set_width(ui.available_width());
-                            ui.heading("Library Compatibility");
-                            ui.add_space(5.0);
-                            egui::ScrollArea::vertical().id_source("library_compatibility_scroll_area").stick_to_bottom(true).show(ui, |ui| {
-                                let log_content = current_state.output_log.lock().expect("Failed to acquire log mutex for library compatibility display");
-                                let filtered_log: String = log_content.lines()
-                                    .filter(|line| {
-                                        line.contains("pip") ||
-                                        line.contains("library") ||
-                                        line.contains("Attempting to install") ||
-                                        line.contains("Successfully installed") ||
-                                        line.contains("Failed to install") ||
-                                        line.contains("Checking library compatibility") ||
-                                        line.contains("Installed version of")
-                                    })
-                                    .collect::<Vec<&str>>()
-                                    .join("\n");
-                                ui.monospace(filtered_log);
-                            });
+        // Show the "Uninstall" dialog (if open)
+        if let Some(dialog) = &mut self.uninstall {
+            let mut close_dialog = false;
+            let mut confirmed = None;
+            let mut back_to_list = false;
+            let vendor_label = vendor_label(&dialog.vendor);
+            if let Some((version, sdk_root)) = &dialog.pending {
+                egui::Window::new(tr(lang, "Uninstall Confirmation"))
+                    .collapsible(false)
+                    .resizable(false)
+                    .auto_sized()
+                    .show(ctx, |ui| {
+                        ui.label(tr_format(lang, "Are you sure you want to uninstall {} {}? This deletes:", &[&vendor_label, version]));
+                        ui.monospace(sdk_root.display().to_string());
+                        ui.add_space(10.0);
+                        ui.horizontal(|ui| {
+                            if ui.button(tr(lang, "Yes, uninstall")).clicked() {
+                                confirmed = Some(sdk_root.clone());
+                            }
+                            if ui.button(tr(lang, "No, keep")).clicked() {
+                                back_to_list = true;
+                            }
                         });
                     });
-                    ui.add_space(10.0);
-                    ui.separator();
-                    ui.add_space(10.0);
-                    ui.heading("Full Output Log (Python related only)");
-                    ui.add_space(5.0);
-                    egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
-                        let log_content = current_state.output_log.lock().expect("Failed to acquire log mutex for full Python log");
-                        ui.monospace(&*log_content); // Display full log for Python, already filtered by vendor context
+            } else {
+                egui::Window::new(tr(lang, "Uninstall"))
+                    .collapsible(false)
+                    .resizable(true)
+                    .show(ctx, |ui| {
+                        if dialog.versions.is_empty() {
+                            ui.label(tr_format(lang, "No installed versions of {} were found.", &[&vendor_label]));
+                        }
+                        for (version, sdk_root) in &dialog.versions {
+                            ui.horizontal(|ui| {
+                                if ui.button(tr(lang, "Uninstall")).clicked() {
+                                    dialog.pending = Some((version.clone(), sdk_root.clone()));
+                                }
+                                ui.label(version);
+                                ui.monospace(sdk_root.display().to_string());
+                            });
+                        }
+                        ui.add_space(10.0);
+                        if ui.button(tr(lang, "Close")).clicked() {
+                            close_dialog = true;
+                        }
                     });
-                } else {
-                    // General log for other vendors
-                    ui.add_space(10.0);
-                    ui.heading(format!("Detailed Output Log ({})", match self.selected_vendor.as_str() {
-                        "azul" => "Java (Azul Zulu)",
-                        "temurin" => "Java (Temurin)",
-                        "openjdk" => "Java (OpenJDK)",
-                        "c_cpp" => "C/C++",
-                        "rust" => "Rust",
-                        "nodejs" => "Node.js",
-                        "go" => "Go",
-                        _ => "Unknown",
-                    }));
-                    ui.add_space(5.0);
-                    egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
-                        let log_content = current_state.output_log.lock().expect("Failed to acquire log mutex for general log");
-                        ui.monospace(&*log_content);
+            }
+            if back_to_list {
+                dialog.pending = None;
+            }
+            if let Some(sdk_root) = confirmed {
+                let vendor = dialog.vendor.clone();
+                if let Some(state) = self.language_states.get_mut(&vendor) {
+                    if state.last_install.as_ref().is_some_and(|installed| installed.path == sdk_root) {
+                        state.last_install = None;
+                    }
+                    let output_log_clone = state.output_log.clone();
+                    let locations = self.install_locations.clone();
+                    let ctx_clone = ctx.clone();
+                    std::thread::spawn(move || {
+                        let (level, message) = match uninstall_sdk(&locations, &vendor, &sdk_root) {
+                            Ok(steps) => (LogLevel::Info, steps.iter().map(|step| format!("{}\n", step)).collect::<String>()),
+                            Err(e) => (LogLevel::Error, format!("Uninstall failed: {}\n", e)),
+                        };
+                        output_log_clone.lock().expect("Failed to acquire log mutex for uninstall").push(level, LogCategory::General, &message);
+                        ctx_clone.request_repaint();
                     });
                 }
-            });
-        });
+                close_dialog = true;
+            }
+            if close_dialog {
+                self.uninstall = None;
+            }
+        }
 
         // Show cancel confirmation dialog (if requested)
         if self.show_cancel_confirmation {
-            egui::Window::new("Cancel Confirmation")
+            egui::Window::new(tr(lang, "Cancel Confirmation"))
                 .collapsible(false)
                 .resizable(false)
                 .auto_sized()
                 .show(ctx, |ui| {
-                    ui.label("Are you sure you want to stop the installation?");
+                    ui.label(tr(lang, "Are you sure you want to stop the installation?"));
                     ui.add_space(10.0);
                     ui.horizontal(|ui| {
-                        if ui.button("Yes, stop").clicked() {
-                            let current_state = self.language_states.get_mut(&self.selected_vendor).expect("Failed to get language state for cancellation");
-                            current_state.cancel_requested.store(true, Ordering::SeqCst);
-                            current_state.is_installing = false;
-                            current_state.install_result = Some(Err("Installation cancelled.".to_string()));
-                            *current_state.output_log.lock().expect("Failed to acquire log mutex to clear cancel log") = String::new(); // Corrected line
-                            current_state.download_progress = 0.0;
-                            current_state.extract_progress = 0.0;
-                            current_state.current_status = "Installation cancelled.".to_string();
+                        if ui.button(tr(lang, "Yes, stop")).clicked() {
+                            self.language_states.get_mut(&self.selected_vendor).expect("Failed to get language state for cancellation").cancel(lang);
                             self.show_cancel_confirmation = false;
                         }
-                        if ui.button("No, continue").clicked() {
+                        if ui.button(tr(lang, "No, continue")).clicked() {
                             self.show_cancel_confirmation = false;
                         }
                     });
                 });
         }
 
+        // Show the "Stop all" confirmation dialog (if requested)
+        if self.show_stop_all_confirmation {
+            egui::Window::new(tr(lang, "Stop all installations"))
+                .collapsible(false)
+                .resizable(false)
+                .auto_sized()
+                .show(ctx, |ui| {
+                    let running: Vec<&str> = VENDOR_TABS.iter()
+                        .filter(|(vendor, _)| self.language_states.get(*vendor).is_some_and(|state| state.is_installing))
+                        .map(|(_, label)| *label)
+                        .collect();
+                    ui.label(tr_format(lang, "Are you sure you want to stop all {} running installations?", &[&running.len()]));
+                    ui.label(running.join(", "));
+                    if self.update_all.as_ref().is_some_and(|dialog| dialog.completed.is_some()) {
+                        ui.label(tr(lang, "The remaining updates of \"Update all installed\" are skipped."));
+                    }
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if ui.button(tr(lang, "Yes, stop all")).clicked() {
+                            if let Some(dialog) = &self.update_all {
+                                dialog.stop_requested.store(true, Ordering::SeqCst);
+                            }
+                            for state in self.language_states.values_mut().filter(|state| state.is_installing) {
+                                state.cancel(lang);
+                            }
+                            self.batch_pending.clear();
+                            self.show_stop_all_confirmation = false;
+                        }
+                        if ui.button(tr(lang, "No, continue")).clicked() {
+                            self.show_stop_all_confirmation = false;
+                        }
+                    });
+                });
+        }
+
         // Show exit confirmation dialog (if requested)
         if self.show_exit_confirmation {
-            egui::Window::new("Exit Confirmation")
+            egui::Window::new(tr(lang, "Exit Confirmation"))
                 .collapsible(false)
                 .resizable(false)
                 .auto_sized()
                 .show(ctx, |ui| {
-                    ui.label("Are you sure you want to exit the application?");
+                    ui.label(tr(lang, "Are you sure you want to exit the application?"));
                     ui.add_space(10.0);
                     ui.horizontal(|ui| {
-                        if ui.button("Yes, exit").clicked() {
+                        if ui.button(tr(lang, "Yes, exit")).clicked() {
                             ctx.send_viewport_cmd(egui::ViewportCommand::Close); // Corrected line for exiting application
                         }
-                        if ui.button("No, stay").clicked() {
+                        if ui.button(tr(lang, "No, stay")).clicked() {
                             self.show_exit_confirmation = false;
                         }
                     });
                 });
         }
+
+        // Save whatever changed this frame: Settings window options, the tab, last-used versions.
+        if self.settings != previous_settings {
+            self.download_limiter.set_max(self.settings.max_parallel_downloads);
+            self.install_locations = self.settings.install_locations();
+            if let Err(e) = save_persisted_settings(&self.settings) {
+                self.toasts.push(Toast::new(tr_format(lang, "Could not save settings: {}", &[&e]), true));
+            }
+        }
+    }
+}
+
+/// The eframe entry point. It shares the single `JdkInstallerApp` with the worker threads (via
+/// `ctx.data`), so progress, status and results they write for any tab are what the UI renders.
+/// It also owns the main-thread-only pieces that must not live behind the shared lock: the
+/// window title it last set and, with the `tray` feature, the tray icon.
+struct SharedApp {
+    app: Arc<Mutex<JdkInstallerApp>>,
+    window_title: String,
+    #[cfg(all(feature = "tray", not(target_os = "linux")))]
+    tray: Option<TrayHandle>,
+    #[cfg(all(feature = "tray", not(target_os = "linux")))]
+    tray_failed: bool, // Creating the icon failed once; don't retry every frame
+}
+
+impl eframe::App for SharedApp {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let shared = self.app.clone();
+        let mut app = shared.lock().expect("Failed to acquire app state lock for UI update");
+        app.update(ctx, frame);
+        self.update_progress_indicators(ctx, &mut app);
+    }
+}
+
+impl SharedApp {
+    /// Mirrors the overall install progress into the window title and, when enabled, the tray
+    /// tooltip, so both can be read while the window is minimized or behind others.
+    fn update_progress_indicators(&mut self, ctx: &egui::Context, app: &mut JdkInstallerApp) {
+        let lang = app.settings.language;
+        let title = match app.overall_install_progress() {
+            Some(progress) => tr_format(lang, "{} - installing {}%", &[&tr(lang, APP_TITLE), &((progress * 100.0).round() as u32)]),
+            None => tr(lang, APP_TITLE).to_string(),
+        };
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+
+        #[cfg(all(feature = "tray", not(target_os = "linux")))]
+        {
+            if !app.settings.tray_enabled {
+                self.tray = None; // Dropping the handle removes the icon
+                self.tray_failed = false;
+            } else if self.tray.is_none() && !self.tray_failed {
+                match TrayHandle::new(ctx, self.app.clone(), lang) {
+                    Ok(tray) => self.tray = Some(tray),
+                    Err(e) => {
+                        app.toasts.push(Toast::new(tr_format(lang, "No tray icon: {}", &[&e]), true));
+                        ctx.request_repaint();
+                        self.tray_failed = true;
+                    }
+                }
+            }
+            if let Some(tray) = &mut self.tray {
+                tray.set_tooltip(&self.window_title);
+            }
+        }
+    }
+}
+
+/// Brings the main window back from the taskbar/dock or from behind other windows.
+#[cfg(all(feature = "tray", not(target_os = "linux")))]
+fn restore_window(ctx: &egui::Context) {
+    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+    ctx.request_repaint();
+}
+
+/// The system tray icon (`tray` feature). Its tooltip carries the same text as the window title;
+/// clicking it or choosing "Restore" brings the window back, and "Quit" goes through the same
+/// exit confirmation as the Exit button.
+#[cfg(all(feature = "tray", not(target_os = "linux")))]
+struct TrayHandle {
+    icon: tray_icon::TrayIcon,
+    tooltip: String,
+}
+
+#[cfg(all(feature = "tray", not(target_os = "linux")))]
+impl TrayHandle {
+    const RESTORE_ID: &'static str = "restore";
+    const QUIT_ID: &'static str = "quit";
+
+    fn new(ctx: &egui::Context, app: Arc<Mutex<JdkInstallerApp>>, lang: Language) -> Result<Self, String> {
+        use tray_icon::menu::{Menu, MenuEvent, MenuItem};
+        use tray_icon::{MouseButton, MouseButtonState, TrayIconEvent};
+
+        let menu = Menu::new();
+        menu.append_items(&[
+            &MenuItem::with_id(Self::RESTORE_ID, tr(lang, "Restore"), true, None),
+            &MenuItem::with_id(Self::QUIT_ID, tr(lang, "Exit"), true, None),
+        ]).map_err(|e| format!("Failed to create tray menu: {}", e))?;
+
+        // A plain accent-coloured square; the app ships no icon assets.
+        let rgba = [100u8, 149, 237, 255].repeat(16 * 16);
+        let image = tray_icon::Icon::from_rgba(rgba, 16, 16).map_err(|e| format!("Failed to create tray icon image: {}", e))?;
+        let icon = tray_icon::TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_icon(image)
+            .with_tooltip(tr(lang, APP_TITLE))
+            .build()
+            .map_err(|e| format!("Failed to create tray icon: {}", e))?;
+
+        // The handlers are process-wide and can only be installed once; the fixed menu ids keep
+        // them valid if the icon is later removed and re-created from the Settings window.
+        let menu_ctx = ctx.clone();
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            if event.id == Self::RESTORE_ID {
+                restore_window(&menu_ctx);
+            } else if event.id == Self::QUIT_ID {
+                app.lock().expect("Failed to acquire app state lock for tray menu").show_exit_confirmation = true;
+                restore_window(&menu_ctx);
+            }
+        }));
+        let click_ctx = ctx.clone();
+        TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+            if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+                restore_window(&click_ctx);
+            }
+        }));
+
+        Ok(TrayHandle { icon, tooltip: tr(lang, APP_TITLE).to_string() })
+    }
+
+    fn set_tooltip(&mut self, tooltip: &str) {
+        if self.tooltip != tooltip && self.icon.set_tooltip(Some(tooltip)).is_ok() {
+            self.tooltip = tooltip.to_string();
+        }
     }
 }
 
 impl JdkInstallerApp {
+    /// Starts installing `vendor` with its tab's configuration on a worker thread, unless it is
+    /// already installing. Workers report through the shared app state in `ctx`, and downloads
+    /// across all of them wait for a slot in `download_limiter`.
+    fn start_install(&mut self, vendor: &str, ctx: &egui::Context) {
+        let lang = self.settings.language;
+        let max_log_kib = self.settings.max_log_kib;
+        let (Some(config), Some(state)) = (self.language_configs.get(vendor), self.language_states.get_mut(vendor)) else {
+            return;
+        };
+        if state.is_installing {
+            return;
+        }
+        if !config.install_latest {
+            // Reached for invalid input only through "Install all selected"; the tab's own button is disabled.
+            if let Err(hint) = validate_version_input(vendor, &config.version) {
+                self.toasts.push(Toast::new(tr_format(lang, "{} not installed: {}", &[&vendor, &tr(lang, hint)]), true));
+                return;
+            }
+        }
+        state.begin_install(lang, vendor, max_log_kib);
+        if takes_version_input(vendor) {
+            self.settings.last_versions.insert(vendor.to_string(), config.version.clone());
+        }
+        let vendor_clone = vendor.to_string();
+        let config_clone = config.clone();
+        let output_log_clone = state.output_log.clone();
+        let ctx_clone = ctx.clone();
+        let cancel_requested_clone = state.cancel_requested.clone();
+        let install_lock = state.install_lock.clone();
+        let install_settings = self.settings.install_settings(self.mirror_config.clone(), self.download_limiter.clone());
+
+        std::thread::spawn(move || {
+            run_install_job(&vendor_clone, &config_clone, output_log_clone, ctx_clone, cancel_requested_clone, install_lock, &install_settings);
+        });
+    }
+
+    /// Installs an interrupted install's version again on its tab. The install replaces the leftover
+    /// directory, and a partial download of the same package continues where it stopped.
+    fn resume_interrupted_install(&mut self, install: &InterruptedInstall, ctx: &egui::Context) {
+        let Some(config) = self.language_configs.get_mut(&install.vendor) else {
+            return;
+        };
+        if takes_version_input(&install.vendor) {
+            // Java tabs take the feature version (`21.0.2` -> `21`), which resolves to the same update while it is the newest.
+            let version = install.version();
+            let version = if is_java_vendor(&install.vendor) { java_feature_version(version) } else { version };
+            config.version = version.to_string();
+            config.install_latest = false;
+        }
+        self.selected_vendor = install.vendor.clone();
+        self.start_install(&install.vendor, ctx);
+    }
+
+    /// Average progress of all installs in flight, or `None` when nothing is installing.
+    fn overall_install_progress(&self) -> Option<f32> {
+        let installing: Vec<&LanguageState> = self.language_states.values().filter(|state| state.is_installing).collect();
+        if installing.is_empty() {
+            return None;
+        }
+        let total: f32 = installing.iter().map(|state| (state.download_progress + state.extract_progress) / 2.0).sum();
+        Some(total / installing.len() as f32)
+    }
+
     fn new(_cc: &eframe::CreationContext<'_>) -> Self {
         let mut language_configs = HashMap::new();
         let mut language_states = HashMap::new();
 
         // Initialize configs and states for all supported languages
-        let vendors = vec![
-            "azul", "temurin", "openjdk", "python", "c_cpp", "rust", "nodejs", "go"
-        ];
-
-        for vendor in vendors {
+        for (vendor, _) in VENDOR_TABS {
             let mut config = LanguageConfig {
                 vendor: vendor.to_owned(),
                 ..Default::default()
             };
             // Set default version based on vendor
             match vendor {
-                "azul" | "temurin" | "openjdk" => config.version = "21".to_owned(),
+                "azul" | "temurin" | "openjdk" | "corretto" | "msopenjdk" | "graalvm" | "liberica" | "sapmachine" | "oracle" => config.version = "21".to_owned(),
                 "python" => config.version = "3.12.4".to_owned(),
+                "kotlin" => config.version = "2.0.21".to_owned(),
+                "ruby" => config.version = "3.3".to_owned(),
+                "deno" | "bun" | "zig" => {
+                    config.version = "".to_owned();
+                    config.install_latest = true; // Latest GitHub release by default
+                },
                 "c_cpp" => {
                     config.version = "".to_owned(); // No specific version input for C/C++
                     config.install_latest = true; // Always install the fixed latest supported version
@@ -1669,12 +2590,16 @@ impl JdkInstallerApp {
                     config.install_latest = true; // Always install latest stable via rustup
                 },
                 "nodejs" => {
-                    config.version = "".to_owned(); // No specific version input for Node.js
-                    config.install_latest = true; // Always install latest LTS
+                    config.version = "".to_owned(); // Picked via "Browse versions" when not installing latest
+                    config.install_latest = true; // Latest LTS by default
                 },
                 "go" => {
-                    config.version = "".to_owned(); // No specific version input for Go
-                    config.install_latest = true; // Always install latest stable
+                    config.version = "".to_owned(); // Picked via "Browse versions" when not installing latest
+                    config.install_latest = true; // Latest stable by default
+                },
+                "erlang" | "elixir" => {
+                    config.version = "".to_owned(); // No specific version input for Erlang/Elixir
+                    config.install_latest = true; // Always install the latest GitHub release
                 },
                 _ => {},
             }
@@ -1682,27 +2607,350 @@ impl JdkInstallerApp {
             language_states.insert(vendor.to_owned(), LanguageState::default());
         }
 
+        let (persisted_settings, settings_warning) = load_persisted_settings();
+        for (vendor, version) in &persisted_settings.last_versions {
+            if let Some(config) = language_configs.get_mut(vendor).filter(|_| takes_version_input(vendor)) {
+                config.version = version.clone();
+            }
+        }
+        let selected_vendor = VENDOR_TABS.iter().map(|(vendor, _)| *vendor)
+            .find(|vendor| *vendor == persisted_settings.selected_tab)
+            .unwrap_or("azul") // Default selected tab
+            .to_owned();
+        for state in language_states.values_mut() {
+            state.current_status = tr(persisted_settings.language, "Ready for installation").to_string();
+        }
+        let (mirror_config, mirror_config_error) = match load_mirror_config() {
+            Ok(config) => (config, None),
+            Err(e) => (MirrorConfig::default(), Some(e)),
+        };
+        let (mirror_rows, mirror_auth_rows) = mirror_config.editable_rows();
+        let install_locations = persisted_settings.install_locations();
+        let interrupted_installs = find_interrupted_installs(&install_locations);
+
         Self {
             language_configs,
             language_states,
-            selected_vendor: "azul".to_owned(), // Default selected tab
-            font_size: 16.0,
+            selected_vendor,
+            download_limiter: Arc::new(DownloadLimiter::new(persisted_settings.max_parallel_downloads)),
+            settings: persisted_settings,
+            install_locations,
+            show_settings: false,
+            mirror_rows,
+            mirror_auth_rows,
+            bundle_path: home_or_fallback_dir().0.join("toolchain-config.zip").display().to_string(),
+            mirror_config: Arc::new(mirror_config),
+            mirror_config_error,
+            toasts: settings_warning.map(|warning| Toast::new(warning, true)).into_iter().collect(),
+            path_repair: None,
+            version_browser: None,
+            update_all: None,
+            batch_selection: BTreeSet::new(),
+            batch_pending: Vec::new(),
+            uninstall: None,
+            interrupted_installs,
+            show_compact_log: false,
+            log_min_level: LogLevel::Info,
+            log_category: None,
             show_cancel_confirmation: false,
+            show_stop_all_confirmation: false,
             show_exit_confirmation: false,
         }
     }
 }
 
+/// An installed toolchain behind its newest release, offered by "Update all installed".
+#[derive(Clone)]
+struct PendingUpdate {
+    vendor: String,
+    installed_version: String, // Newest installed version
+    latest_version: String,
+}
+
+/// What the "Update all installed" scan found: the updates to offer, plus one note per
+/// installed toolchain that is current or could not be checked.
+struct UpdateScan {
+    updates: Vec<PendingUpdate>,
+    notes: Vec<String>,
+}
+
+/// Newest release of `vendor`, in the form its install directories are named after. Java vendors
+/// report the newest update of the `installed` feature release (e.g. "21.0.5+11" for "21.0.2"), as
+/// an update never moves a JDK to another feature release.
+/// None for vendors whose releases can't be compared this way (release-labelled or license-gated).
+fn latest_release_version(vendor: &str, installed: &str, proxy: &str) -> Option<Result<String, String>> {
+    let result = match vendor {
+        _ if is_java_vendor(vendor) => latest_java_update(java_feature_version(installed), proxy),
+        "python" => get_latest_python_version(proxy).map(|(version, _)| version),
+        // The Node.js tab installs the newest LTS release.
+        "nodejs" => fetch_available_versions(vendor, proxy).and_then(|versions| {
+            versions.into_iter().find(|release| release.lts == Some(true)).map(|release| release.version)
+                .ok_or_else(|| "No Node.js LTS release found".to_string())
+        }),
+        "erlang" | "elixir" | "kotlin" | "deno" | "bun" => {
+            let repo = match vendor {
+                "erlang" => "erlang/otp",
+                "elixir" => "elixir-lang/elixir",
+                "kotlin" => "JetBrains/kotlin",
+                "deno" => "denoland/deno",
+                _ => "oven-sh/bun",
+            };
+            build_http_client(API_TIMEOUT_SECS, proxy)
+                .and_then(|client| get_latest_github_release(&client, repo))
+                .map(|release| release.tag_name.trim_start_matches("OTP-").trim_start_matches("bun-").trim_start_matches('v').to_string())
+        }
+        _ if offers_version_browser(vendor) => fetch_available_versions(vendor, proxy).and_then(|versions| {
+            versions.into_iter().next().map(|release| release.version)
+                .ok_or_else(|| format!("No {} releases found", vendor))
+        }),
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Compares the newest installed version of every vendor with its latest release.
+fn scan_for_updates(locations: &InstallLocations, proxy: &str) -> UpdateScan {
+    let mut scan = UpdateScan { updates: Vec::new(), notes: Vec::new() };
+    for (vendor, _) in VENDOR_TABS {
+        // Skip directories not named after a plain version, such as Miniconda installs.
+        let Some((installed_version, _)) = installed_versions(locations, vendor).into_iter()
+            .find(|(version, _)| version.starts_with(|c: char| c.is_ascii_digit())) else {
+            continue;
+        };
+        match latest_release_version(vendor, &installed_version, proxy) {
+            None => scan.notes.push(format!("{} {}: update checks are not supported for this vendor", vendor, installed_version)),
+            Some(Err(e)) => scan.notes.push(format!("{} {}: could not check for updates: {}", vendor, installed_version, e)),
+            Some(Ok(latest_version)) if is_outdated(vendor, &installed_version, &latest_version) => {
+                scan.updates.push(PendingUpdate { vendor: vendor.to_string(), installed_version, latest_version });
+            }
+            Some(Ok(_)) => scan.notes.push(format!("{} {} is up to date", vendor, installed_version)),
+        }
+    }
+    scan
+}
+
+/// Runs "Update all installed" jobs one after another, each in its vendor's tab, counting
+/// finished jobs in `completed`. Tabs that are busy with another install are skipped, and so is
+/// every job not yet started once `stop_requested` is set.
+fn run_update_batch(jobs: Vec<(String, LanguageConfig)>, completed: Arc<AtomicUsize>, stop_requested: Arc<AtomicBool>, ctx: egui::Context, settings: InstallSettings) {
+    let app_state_id = egui::Id::new("JdkInstallerAppState");
+    for (vendor, config) in jobs {
+        if stop_requested.load(Ordering::SeqCst) {
+            completed.fetch_add(1, Ordering::SeqCst); // Counted as done, so the dialog reaches its end
+            continue;
+        }
+        let job_state = ctx.data(|d| d.get_temp::<Arc<Mutex<JdkInstallerApp>>>(app_state_id)).and_then(|app_state_arc| {
+            let mut app_state = app_state_arc.lock().expect("Failed to acquire app state lock for batch update");
+            let lang = app_state.settings.language;
+            let max_log_kib = app_state.settings.max_log_kib;
+            let lang_state = app_state.language_states.get_mut(&vendor)?;
+            if lang_state.is_installing {
+                return None;
+            }
+            lang_state.begin_install(lang, &vendor, max_log_kib);
+            Some((lang_state.output_log.clone(), lang_state.cancel_requested.clone(), lang_state.install_lock.clone()))
+        });
+        if let Some((output_log, cancel_requested, install_lock)) = job_state {
+            run_install_job(&vendor, &config, output_log, ctx.clone(), cancel_requested, install_lock, &settings);
+        }
+        completed.fetch_add(1, Ordering::SeqCst);
+        ctx.request_repaint();
+    }
+}
+
+/// `env --vendor <id> [--version <v>] [--shell bash|zsh|cmd|powershell]`: prints the statements that
+/// put an installed toolchain on PATH (plus JAVA_HOME/GOROOT/PYTHON_HOME), e.g. for
+/// `eval "$(jdkm env --vendor go)"`. Nothing is modified. Without `--version` the `current` link is
+/// used when set, else the newest install; a version prefix such as "21" picks the newest
+/// matching install.
+fn run_env_command(args: &[String]) -> Result<String, String> {
+    let mut vendor: Option<&str> = None;
+    let mut version: Option<&str> = None;
+    let mut shell = if cfg!(windows) { "powershell" } else { "bash" };
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        let mut value = || args_iter.next().map(String::as_str).ok_or_else(|| format!("{} requires a value", arg));
+        match arg.as_str() {
+            "--vendor" => vendor = Some(value()?),
+            "--version" => version = Some(value()?),
+            "--shell" => shell = value()?,
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+    let usage = format!("Usage: {} env --vendor <vendor> [--version <version>] [--shell bash|zsh|cmd|powershell]", env!("CARGO_PKG_NAME"));
+    let vendor = vendor.ok_or(usage)?;
+    if !VENDOR_TABS.iter().any(|(id, _)| *id == vendor) {
+        return Err(format!("Unknown vendor: {}", vendor));
+    }
+    let os_name = if cfg!(windows) { "windows" } else { "" }; // Only the Windows layouts differ
+    let locations = load_persisted_settings().0.install_locations();
+
+    let sdk_root = if vendor == "rust" {
+        dirs::home_dir().ok_or_else(|| "Could not find home directory for .cargo path.".to_string())?.join(".cargo")
+    } else if let (None, Some(current)) = (version, current_version_dir(&locations, vendor)) {
+        current
+    } else {
+        installed_versions(&locations, vendor).into_iter()
+            .find(|(installed, _)| version.is_none_or(|wanted| installed == wanted || installed.starts_with(&format!("{}.", wanted))))
+            .map(|(_, path)| path)
+            .ok_or_else(|| match version {
+                Some(wanted) => format!("No {} {} installation found under {}", vendor, wanted, locations.root.display()),
+                None => format!("No {} installation found under {}", vendor, locations.root.display()),
+            })?
+    };
+
+    let variables: Vec<(&str, PathBuf)> = sdk_home_variable(vendor).map(|name| (name, sdk_root.clone())).into_iter().collect();
+    let bin_dir = sdk_bin_dir(vendor, &sdk_root, os_name);
+
+    let mut output = String::new();
+    match shell {
+        "bash" | "zsh" | "sh" => {
+            let quote = |path: &Path| path.display().to_string().replace('\\', "\\\\").replace('"', "\\\"").replace('$', "\\$").replace('`', "\\`");
+            for (name, path) in &variables {
+                output.push_str(&format!("export {}=\"{}\"\n", name, quote(path)));
+            }
+            output.push_str(&format!("export PATH=\"{}:$PATH\"\n", quote(&bin_dir)));
+        }
+        "cmd" => {
+            for (name, path) in &variables {
+                output.push_str(&format!("set \"{}={}\"\n", name, path.display()));
+            }
+            output.push_str(&format!("set \"PATH={};%PATH%\"\n", bin_dir.display()));
+        }
+        "powershell" | "pwsh" => {
+            let quote = |path: &Path| path.display().to_string().replace('`', "``").replace('"', "`\"").replace('$', "`$");
+            for (name, path) in &variables {
+                output.push_str(&format!("$env:{} = \"{}\"\n", name, quote(path)));
+            }
+            output.push_str(&format!("$env:PATH = \"{};\" + $env:PATH\n", quote(&bin_dir)));
+        }
+        other => return Err(format!("Unsupported shell: {} (expected bash, zsh, cmd or powershell)", other)),
+    }
+    Ok(output)
+}
+
+/// `install --vendor <id> (--version <v> | --latest) [--libs <specs>] ...`: installs without the GUI,
+/// printing the install log to stdout. Accepts exactly the flags of the tab's copied CLI command
+/// (see LanguageConfig::to_cli_command()) and uses the saved Settings and mirrors.
+fn run_install_command(args: &[String]) -> Result<InstalledSdk, String> {
+    let mut config = LanguageConfig::default();
+    let mut vendor: Option<&str> = None;
+    let mut version: Option<&str> = None;
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        let mut value = || args_iter.next().map(String::as_str).ok_or_else(|| format!("{} requires a value", arg));
+        match arg.as_str() {
+            "--vendor" => vendor = Some(value()?),
+            "--version" => version = Some(value()?),
+            "--latest" => config.install_latest = true,
+            "--jvm-impl" => config.jvm_impl = value()?.to_string(),
+            "--release-type" => config.release_type = value()?.to_string(),
+            "--javafx" => config.include_javafx = true,
+            "--accept-license" => config.license_accepted = true,
+            "--distribution" => config.python_distribution = value()?.to_string(),
+            "--libs" => config.python_libraries_input = value()?.to_string(),
+            "--graalvm-components" => {
+                config.graalvm_components = value()?.split(',').map(str::trim).filter(|c| !c.is_empty()).map(str::to_string).collect();
+            }
+            "--add-to-path" => config.add_to_path = true,
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+    let usage = format!(
+        "Usage: {} install --vendor <vendor> (--version <version> | --latest) [--jvm-impl hotspot|openj9] [--release-type ga|ea] [--javafx] [--accept-license] [--distribution python.org|miniconda] [--libs <specs>] [--graalvm-components native-image,js,python] [--add-to-path]",
+        env!("CARGO_PKG_NAME")
+    );
+    let vendor = vendor.ok_or(usage.clone())?;
+    if !VENDOR_TABS.iter().any(|(id, _)| *id == vendor) {
+        return Err(format!("Unknown vendor: {}", vendor));
+    }
+    match (version, config.install_latest) {
+        (Some(version), false) => {
+            validate_version_input(vendor, version)?;
+            config.version = version.to_string();
+        }
+        (None, true) => config.version.clear(),
+        _ => return Err(usage),
+    }
+    if !ADOPTIUM_JVM_IMPLS.contains(&config.jvm_impl.as_str()) {
+        return Err(format!("Unknown JVM implementation: {} (expected {})", config.jvm_impl, ADOPTIUM_JVM_IMPLS.join(" or ")));
+    }
+    if let Some(unknown) = config.graalvm_components.iter().find(|c| !GRAALVM_COMPONENTS.iter().any(|(id, _, _)| id == c)) {
+        return Err(format!("Unknown GraalVM component: {} (expected native-image, js or python)", unknown));
+    }
+    if !PYTHON_DISTRIBUTIONS.iter().any(|(id, _)| *id == config.python_distribution) {
+        return Err(format!("Unknown Python distribution: {} (expected python.org or miniconda)", config.python_distribution));
+    }
+    config.vendor = vendor.to_string();
+
+    let (persisted_settings, settings_warning) = load_persisted_settings();
+    if let Some(warning) = settings_warning {
+        eprintln!("{}", warning);
+    }
+    let mirror_config = load_mirror_config()?;
+    let settings = persisted_settings.install_settings(Arc::new(mirror_config), Arc::new(DownloadLimiter::new(1)));
+    let mut log = InstallLog::new();
+    log.restart(vendor, persisted_settings.max_log_kib * 1024);
+    run_installation_logic(&config, &StdoutProgress { log: Mutex::new(log) }, Arc::new(AtomicBool::new(false)), &settings)
+}
+
+/// Prints an installation's log to stdout for the headless `install` command (and writes the
+/// vendor's log file); the status line and progress bars only exist in the GUI.
+struct StdoutProgress {
+    log: Mutex<InstallLog>,
+}
+
+impl ProgressSink for StdoutProgress {
+    fn set_status(&self, _status: &Status) {}
+    fn set_download_progress(&self, _progress: f32) {}
+    fn set_extract_progress(&self, _progress: f32) {}
+
+    fn log_entry(&self, level: LogLevel, category: LogCategory, text: &str) {
+        print!("{}{}", level_prefix(level), text);
+        let _ = io::stdout().flush(); // Progress lines should show up as they happen, even when piped
+        self.log.lock().expect("Failed to acquire log mutex for stdout log").push(level, category, text);
+    }
+}
+
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("install") {
+        match run_install_command(&args[1..]) {
+            Ok(installed) => println!("Installed {} {} at {}", installed.vendor, installed.version, installed.path.display()),
+            Err(e) => {
+                eprintln!("ERROR: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if args.first().map(String::as_str) == Some("env") {
+        match run_env_command(&args[1..]) {
+            Ok(statements) => print!("{}", statements),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     let native_options = eframe::NativeOptions::default();
     eframe::run_native(
-        "Multi-Language Installer", // Updated window title
+        APP_TITLE,
         native_options,
         Box::new(|cc| {
             let app = Arc::new(Mutex::new(JdkInstallerApp::new(cc)));
             // Store the Arc<Mutex<JdkInstallerApp>> in egui's data store.
             cc.egui_ctx.data_mut(|d| d.insert_temp(egui::Id::new("JdkInstallerAppState"), app.clone()));
-            Ok(Box::new(JdkInstallerApp::new(cc)))
+            Ok(Box::new(SharedApp {
+                app,
+                window_title: APP_TITLE.to_string(),
+                #[cfg(all(feature = "tray", not(target_os = "linux")))]
+                tray: None,
+                #[cfg(all(feature = "tray", not(target_os = "linux")))]
+                tray_failed: false,
+            }))
         }),
     ).expect("eframe application failed to run");
-}
\ No newline at end of file
+}